@@ -0,0 +1,42 @@
+use std::{env, error::Error, fs, path::Path};
+
+const SOLUTIONS_DIR: &str = "src/solutions";
+
+fn is_day_module(name: &str) -> bool {
+    name.strip_prefix("day_")
+        .is_some_and(|day| !day.is_empty() && day.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("cargo:rerun-if-changed={}", SOLUTIONS_DIR);
+    let mut days = Vec::new();
+    for entry in fs::read_dir(SOLUTIONS_DIR)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "rs") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if is_day_module(name) {
+            days.push(name.to_string());
+        }
+    }
+    days.sort();
+
+    let day_names = days.join(",\n            ");
+    let generated = format!(
+        "macro_rules! with_days {{
+    ($macro:ident) => {{
+        $macro! {{
+            {},
+        }}
+    }};
+}}
+",
+        day_names
+    );
+    let out_path = Path::new(&env::var("OUT_DIR")?).join("days.rs");
+    fs::write(out_path, generated)?;
+    Ok(())
+}
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use aoc_utils::{formatted_struct, DaySolution, MyResult};
 
 formatted_struct! {
+    #[longest_match]
     #[derive(Debug)]
     pub enum Instruction {
         Set {
@@ -28,8 +29,8 @@ formatted_struct! {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(format!("Hello {:?}", *input))
     }
 }
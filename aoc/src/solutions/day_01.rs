@@ -22,8 +22,8 @@ formatted_struct! {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut firsts = input.lines.iter().map(|l| l.first).collect::<Vec<_>>();
         let mut seconds = input.lines.iter().map(|l| l.second).collect::<Vec<_>>();
         firsts.sort();
@@ -35,7 +35,7 @@ impl DaySolution for Solution {
             .sum::<i64>();
         Ok(delta_sum)
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut second_counts = HashMap::<i64, i64>::new();
         let firsts = input.lines.iter().map(|l| l.first).collect::<Vec<_>>();
         for second in input.lines.iter().map(|l| l.second) {
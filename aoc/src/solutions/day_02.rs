@@ -59,12 +59,12 @@ impl Report {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let safe_count = input.reports.iter().filter(|x| x.is_safe()).count();
         Ok(safe_count)
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let safe_count = input
             .reports
             .iter()
@@ -1,50 +1,43 @@
 use std::fmt::Debug;
 
-use aoc_utils::{DaySolution, MyResult, Parsable};
-use regex::{Captures, Regex};
+use aoc_utils::{DaySolution, Matches, MyResult, Parsable};
 
-pub struct Solution;
-
-fn extract(m: &Captures, name: &str) -> MyResult<i32> {
-    let text = m.name(name).ok_or(format!("{} is missing", name))?.as_str();
-
-    i32::parse(text)
+#[derive(Debug, Parsable)]
+pub enum Instruction {
+    #[parse(captures = r"do\(\)")]
+    Do,
+    #[parse(captures = r"don't\(\)")]
+    Dont,
+    #[parse(captures = r"mul\((?<lh>\d{1,3}),(?<rh>\d{1,3})\)")]
+    Mul { lh: i32, rh: i32 },
 }
 
+pub struct Solution;
+
 impl DaySolution for Solution {
-    type InputFormat = String;
-    fn solve_1(input: &String) -> MyResult<impl Debug + 'static> {
-        let instruction_matcher = Regex::new("mul\\((?<lh>\\d{1,3}),(?<rh>\\d{1,3})\\)")?;
-        let matches = instruction_matcher
-            .captures_iter(input)
-            .map(|m| Ok(extract(&m, "lh")? * extract(&m, "rh")?))
-            .collect::<MyResult<Vec<_>>>()?;
-        Ok(matches.into_iter().sum::<i32>())
+    type InputFormat<'a> = Matches<Instruction>;
+    fn solve_1(input: &Matches<Instruction>) -> MyResult<impl Debug + use<>> {
+        Ok(input
+            .0
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::Mul { lh, rh } => lh * rh,
+                _ => 0,
+            })
+            .sum::<i32>())
     }
-    fn solve_2(input: &String) -> MyResult<impl Debug + 'static> {
-        let instruction_matcher = Regex::new(
-            "(?<do>do\\(\\))|(?<dont>don't\\(\\))|mul\\((?<lh>\\d{1,3}),(?<rh>\\d{1,3})\\)",
-        )?;
+    fn solve_2(input: &Matches<Instruction>) -> MyResult<impl Debug + use<>> {
         let mut enabled = true;
-        let matches = instruction_matcher
-            .captures_iter(input)
-            .map(|m| {
-                if m.name("do").is_some() {
-                    enabled = true;
-                    return Ok(0);
-                }
-                if m.name("dont").is_some() {
-                    enabled = false;
-                    return Ok(0);
-                }
-                if !enabled {
-                    return Ok(0);
-                }
-                Ok(extract(&m, "lh")? * extract(&m, "rh")?)
-            })
-            .collect::<MyResult<Vec<_>>>()?;
-        // Ok(format!("{:?}", matches))
-        Ok(matches.into_iter().sum::<i32>())
+        let mut sum = 0;
+        for instruction in &input.0 {
+            match instruction {
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
+                Instruction::Mul { lh, rh } if enabled => sum += lh * rh,
+                Instruction::Mul { .. } => {}
+            }
+        }
+        Ok(sum)
     }
     fn preferred_sample_input() -> i32 {
         1
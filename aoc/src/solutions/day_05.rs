@@ -3,7 +3,7 @@ use std::{
     fmt::Debug,
 };
 
-use aoc_utils::{formatted_struct, DaySolution, MyResult, Parsable};
+use aoc_utils::{formatted_struct, try_from_parsable, DaySolution, MyResult};
 
 formatted_struct! {
     #[derive(Debug)]
@@ -14,36 +14,28 @@ formatted_struct! {
     }
 }
 
-formatted_struct! {
-    #[derive(Debug, Clone)]
-    pub struct Update {
-        #[separated_by=","]
-        page_numbers: Vec<i32>,
-    }
-}
-
 formatted_struct! {
     #[derive(Debug)]
     pub struct InputFormat {
         #[separated_by="\n"]
         ordering_rules: Vec<OrderingRule>,
         "\n\n",
-        #[separated_by="\n"]
-        updates : Vec<Update>,
+        #[separated_by("\n", ",")]
+        updates: Vec<Vec<i32>>,
     }
 }
 
 pub struct ProcessedInput {
     page_to_pages_after: HashMap<i32, HashSet<i32>>,
-    updates: Vec<Update>,
+    updates: Vec<Vec<i32>>,
 }
 
-impl Parsable for ProcessedInput {
-    fn parse(text: &str) -> MyResult<Self> {
+impl From<InputFormat> for ProcessedInput {
+    fn from(input: InputFormat) -> Self {
         let InputFormat {
             updates,
             ordering_rules,
-        } = InputFormat::parse(text)?;
+        } = input;
         let mut page_to_pages_after = HashMap::new();
 
         for &OrderingRule {
@@ -56,17 +48,19 @@ impl Parsable for ProcessedInput {
                 .or_insert_with(HashSet::new)
                 .insert(page_after);
         }
-        Ok(ProcessedInput {
+        ProcessedInput {
             page_to_pages_after,
             updates,
-        })
+        }
     }
 }
 
+try_from_parsable!(ProcessedInput, InputFormat);
+
 impl ProcessedInput {
-    fn check_update(&self, update: &Update) -> bool {
-        let mut past_pages = HashSet::with_capacity(update.page_numbers.len());
-        for &page in &update.page_numbers {
+    fn check_update(&self, page_numbers: &[i32]) -> bool {
+        let mut past_pages = HashSet::with_capacity(page_numbers.len());
+        for &page in page_numbers {
             if let Some(pages_after) = self.page_to_pages_after.get(&page) {
                 if !past_pages.is_disjoint(pages_after) {
                     return false;
@@ -76,10 +70,10 @@ impl ProcessedInput {
         }
         true
     }
-    fn sort_update(&self, update: &Update) -> Update {
-        let mut sorted_page_numbers = Vec::with_capacity(update.page_numbers.len());
+    fn sort_update(&self, page_numbers: &[i32]) -> Vec<i32> {
+        let mut sorted_page_numbers = Vec::with_capacity(page_numbers.len());
 
-        for &current_page in &update.page_numbers {
+        for &current_page in page_numbers {
             let insert_index = sorted_page_numbers
                 .iter()
                 .enumerate()
@@ -95,33 +89,31 @@ impl ProcessedInput {
                 .unwrap_or(sorted_page_numbers.len());
             sorted_page_numbers.insert(insert_index, current_page);
         }
-        Update {
-            page_numbers: sorted_page_numbers,
-        }
+        sorted_page_numbers
     }
 }
 
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = ProcessedInput;
-    fn solve_1(input: &ProcessedInput) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = ProcessedInput;
+    fn solve_1(input: &ProcessedInput) -> MyResult<impl Debug + use<>> {
         let result = input
             .updates
             .iter()
             .filter(|u| input.check_update(u))
-            .map(|Update { page_numbers }| page_numbers[page_numbers.len() / 2])
+            .map(|page_numbers| page_numbers[page_numbers.len() / 2])
             .sum::<i32>();
 
         Ok(result)
     }
-    fn solve_2(input: &ProcessedInput) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &ProcessedInput) -> MyResult<impl Debug + use<>> {
         let result = input
             .updates
             .iter()
             .filter(|u| !input.check_update(u))
             .map(|u| input.sort_update(u))
-            .map(|Update { page_numbers }| page_numbers[page_numbers.len() / 2])
+            .map(|page_numbers| page_numbers[page_numbers.len() / 2])
             // .collect::<Vec<_>>();
             .sum::<i32>();
 
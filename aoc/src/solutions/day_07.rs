@@ -89,11 +89,11 @@ impl InputFormat {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(input.solve(false))
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(input.solve(true))
     }
 }
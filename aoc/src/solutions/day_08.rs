@@ -3,53 +3,42 @@ use std::{
     fmt::Debug,
 };
 
-use aoc_utils::{formatted_struct, DaySolution, MyResult, Parsable};
-
-formatted_struct! {
-    #[derive(Debug)]
-    pub struct RawInputFormat {
-        #[separated_by="\n"]
-        rows: Vec<String>,
-    }
-}
+use aoc_utils::{try_from_parsable, DaySolution, Grid, MyResult, Point};
 
 #[derive(Debug)]
 pub struct ParsedInputFormat {
-    antenna_locations: HashMap<char, Vec<(i32, i32)>>,
-    n_rows: i32,
-    n_cols: i32,
+    antenna_locations: HashMap<char, Vec<Point>>,
+    n_rows: isize,
+    n_cols: isize,
 }
 
-impl Parsable for ParsedInputFormat {
-    fn parse(text: &str) -> MyResult<Self> {
-        let raw = RawInputFormat::parse(text)?;
-        let n_rows = raw.rows.len() as i32;
-        let n_cols = raw.rows[0].len() as i32;
+impl From<Grid<char>> for ParsedInputFormat {
+    fn from(grid: Grid<char>) -> Self {
         let mut antenna_locations = HashMap::new();
-        for (i, row) in raw.rows.into_iter().enumerate() {
-            for (j, char) in row.chars().enumerate() {
-                if char == '.' {
-                    continue;
-                }
-                antenna_locations
-                    .entry(char)
-                    .or_insert_with(Vec::new)
-                    .push((i as i32, j as i32));
+        for (point, &char) in grid.iter() {
+            if char == '.' {
+                continue;
             }
+            antenna_locations
+                .entry(char)
+                .or_insert_with(Vec::new)
+                .push(point);
         }
-        Ok(ParsedInputFormat {
+        ParsedInputFormat {
             antenna_locations,
-            n_rows,
-            n_cols,
-        })
+            n_rows: grid.height() as isize,
+            n_cols: grid.width() as isize,
+        }
     }
 }
 
+try_from_parsable!(ParsedInputFormat, Grid<char>);
+
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = ParsedInputFormat;
-    fn solve_1(input: &ParsedInputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = ParsedInputFormat;
+    fn solve_1(input: &ParsedInputFormat) -> MyResult<impl Debug + use<>> {
         let mut antinodes = HashSet::new();
         for antenna_locaations in input.antenna_locations.values() {
             for d_1_location in antenna_locaations {
@@ -69,7 +58,7 @@ impl DaySolution for Solution {
             .count();
         Ok(antinodes_on_map)
     }
-    fn solve_2(input: &ParsedInputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &ParsedInputFormat) -> MyResult<impl Debug + use<>> {
         let mut antinodes = HashSet::new();
         for (&_antena_name, antenna_locaations) in &input.antenna_locations {
             for &d_1_location in antenna_locaations {
@@ -1,28 +1,18 @@
 use std::fmt::Debug;
 
-use aoc_utils::{DaySolution, MyResult};
+use aoc_utils::{DaySolution, Digits, MyResult};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 pub struct Solution;
 
-fn to_disk_map(input: &str) -> Vec<Option<i32>> {
-    const ZERO: i32 = '0' as i32;
-    let mut char_iterator = input.chars();
+// The digits alternate between the size of a file and the empty space after it.
+fn to_disk_map(input: &Digits) -> Vec<Option<i32>> {
     let mut result = Vec::new();
-    let mut next_id = 0;
-    while let Some(file_blocks) = char_iterator.next() {
-        let file_blocks = file_blocks as i32 - ZERO;
-        for _ in 0..file_blocks {
-            result.push(Some(next_id));
+    for (id, chunk) in input.0.chunks(2).enumerate() {
+        result.extend(std::iter::repeat_n(Some(id as i32), chunk[0] as usize));
+        if let Some(&empty_blocks) = chunk.get(1) {
+            result.extend(std::iter::repeat_n(None, empty_blocks as usize));
         }
-        let Some(empty_blocks) = char_iterator.next() else {
-            break;
-        };
-        let empty_blocks = empty_blocks as i32 - ZERO;
-
-        for _ in 0..empty_blocks {
-            result.push(None);
-        }
-        next_id += 1;
     }
     result
 }
@@ -72,24 +62,18 @@ enum DiskChunk {
     Empty { size: i32 },
 }
 
-fn to_disk_map_2(input: &str) -> Vec<DiskChunk> {
-    const ZERO: i32 = '0' as i32;
-    let mut char_iterator = input.chars();
+fn to_disk_map_2(input: &Digits) -> Vec<DiskChunk> {
     let mut result = Vec::new();
-    let mut next_id = 0;
-    while let Some(file_blocks) = char_iterator.next() {
-        let file_blocks = file_blocks as i32 - ZERO;
+    for (id, chunk) in input.0.chunks(2).enumerate() {
         result.push(DiskChunk::File {
-            id: next_id,
-            size: file_blocks,
+            id: id as i32,
+            size: chunk[0] as i32,
         });
-        let Some(empty_blocks) = char_iterator.next() else {
-            break;
-        };
-        let empty_blocks = empty_blocks as i32 - ZERO;
-
-        result.push(DiskChunk::Empty { size: empty_blocks });
-        next_id += 1;
+        if let Some(&empty_blocks) = chunk.get(1) {
+            result.push(DiskChunk::Empty {
+                size: empty_blocks as i32,
+            });
+        }
     }
     result
 }
@@ -158,18 +142,28 @@ fn compute_checksum_2(disk_map: &[DiskChunk]) -> i64 {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = String;
-    fn solve_1(input: &String) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = Digits;
+    fn solve_1(input: &Digits) -> MyResult<impl Debug + use<>> {
         let disk_map = to_disk_map(input);
         let compacted = compact(disk_map);
         let checksum = compute_checksum(&compacted);
         Ok(checksum)
     }
-    fn solve_2(input: &String) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &Digits) -> MyResult<impl Debug + use<>> {
         let disk_map = to_disk_map_2(input);
         let compacted = compact_2(disk_map);
         // return Ok(compacted);
         let checksum = compute_checksum_2(&compacted);
         Ok(checksum)
     }
+    fn generate(seed: u64, size: usize) -> MyResult<String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let disk_map = (0..(size | 1))
+            .map(|i| {
+                let min_blocks = if i % 2 == 0 { 1 } else { 0 };
+                char::from(b'0' + rng.gen_range(min_blocks..=9))
+            })
+            .collect::<String>();
+        Ok(disk_map)
+    }
 }
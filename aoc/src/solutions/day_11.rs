@@ -48,7 +48,7 @@ fn generate_all_stone_transitions(
     let mut result = HashMap::new();
 
     while let Some(stone) = stones_left.pop() {
-        let new_stones = blink(&[stone.clone()]);
+        let new_stones = blink(std::slice::from_ref(&stone));
         let mut next_stones = HashMap::new();
         for new_stone in new_stones {
             *next_stones.entry(new_stone.clone()).or_insert(0) += 1i64;
@@ -84,11 +84,11 @@ fn solve(initial_stones: &[String], n_blinks: i32) -> i64 {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(solve(&input.stones, 25))
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(solve(&input.stones, 75))
     }
     fn preferred_sample_input() -> i32 {
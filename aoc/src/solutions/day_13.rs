@@ -1,8 +1,10 @@
 use std::fmt::Debug;
 
-use aoc_utils::{formatted_struct, make_recursive_fn, DaySolution, MyResult};
+use aoc_utils::{formatted_struct, make_recursive_fn, DaySolution, MyResult, Variant};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 formatted_struct! {
+    #[display]
     #[derive(Debug)]
     pub struct ButtonMoves {
         "X\\+",
@@ -14,6 +16,7 @@ formatted_struct! {
 }
 
 formatted_struct! {
+    #[display]
     #[derive(Debug)]
     pub struct ClawMachine {
         "Button A: ",
@@ -28,6 +31,7 @@ formatted_struct! {
 }
 
 formatted_struct! {
+    #[display]
     #[derive(Debug)]
     pub struct InputFormat {
         #[separated_by="\n\n"]
@@ -49,7 +53,7 @@ fn mul(m: [[i64; 2]; 2], v: [i64; 2]) -> [i64; 2] {
 }
 
 impl ClawMachine {
-    fn _solve_1(&self) -> Option<i64> {
+    fn memo_solve_1(&self) -> Option<i64> {
         let mut memo = make_recursive_fn(
             |rec_memo: &mut dyn FnMut((i64, i64)) -> Option<i64>, (x, y)| {
                 if x == 0 && y == 0 {
@@ -96,8 +100,8 @@ impl ClawMachine {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let results = input
             .claw_machines
             .iter()
@@ -106,7 +110,16 @@ impl DaySolution for Solution {
         // Ok(results)
         Ok(results.into_iter().flatten().sum::<i64>())
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn variants_1() -> Vec<Variant<Self>> {
+        vec![Variant::new("memo_solve_1", |input: &InputFormat| {
+            Ok(input
+                .claw_machines
+                .iter()
+                .flat_map(ClawMachine::memo_solve_1)
+                .sum::<i64>())
+        })]
+    }
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let results = input
             .claw_machines
             .iter()
@@ -115,4 +128,39 @@ impl DaySolution for Solution {
         // Ok(results)
         Ok(results.into_iter().flatten().sum::<i64>())
     }
+    fn generate(seed: u64, size: usize) -> MyResult<String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let claw_machines = (0..size)
+            .map(|_| {
+                // Buttons moving the same way have no determinant, which `math_solve` can't handle.
+                let [a_x, a_y, b_x, b_y] = loop {
+                    let deltas = [(); 4].map(|_| rng.gen_range(10..100i64));
+                    let [a_x, a_y, b_x, b_y] = deltas;
+                    if a_x * b_y != a_y * b_x {
+                        break deltas;
+                    }
+                };
+                let [a_presses, b_presses] = [(); 2].map(|_| rng.gen_range(0..=100));
+                let mut prize_x = a_presses * a_x + b_presses * b_x;
+                let mut prize_y = a_presses * a_y + b_presses * b_y;
+                if rng.gen_bool(0.5) {
+                    prize_x += rng.gen_range(1..100);
+                    prize_y += rng.gen_range(1..100);
+                }
+                ClawMachine {
+                    a: ButtonMoves {
+                        x_delta: a_x,
+                        y_delta: a_y,
+                    },
+                    b: ButtonMoves {
+                        x_delta: b_x,
+                        y_delta: b_y,
+                    },
+                    prize_x,
+                    prize_y,
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(InputFormat { claw_machines }.to_string() + "\n")
+    }
 }
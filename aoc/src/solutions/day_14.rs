@@ -1,26 +1,28 @@
 use std::fmt::Debug;
 
-use aoc_utils::{formatted_struct, DaySolution, MyResult};
+use aoc_utils::{DaySolution, MyResult, Parsable};
 
-formatted_struct! {
-    #[derive(Debug)]
-    pub struct Robot {
-        "p=",
-        p_x: i64,
-        ",",
-        p_y: i64,
-        " v=",
-        v_x: i64,
-        ",",
-        v_y:i64,
-    }
+#[derive(Debug, Parsable)]
+#[parse(format = "p={p_x},{p_y} v={v_x},{v_y}")]
+pub struct Robot {
+    p_x: i64,
+    p_y: i64,
+    v_x: i64,
+    v_y: i64,
+}
+
+#[derive(Debug, Parsable)]
+pub struct Bathroom {
+    #[parse(sep = "\n")]
+    #[validate(with = known_size)]
+    robots: Vec<Robot>,
 }
 
-formatted_struct! {
-    #[derive(Debug)]
-    pub struct Bathroom {
-        #[separated_by="\n"]
-        robots: Vec<Robot>,
+// `size` only knows the sample and the real bathroom.
+fn known_size(robots: &[Robot]) -> Result<(), String> {
+    match robots.len() {
+        12 | 500 => Ok(()),
+        count => Err(format!("don't know the bathroom size for {} robots", count)),
     }
 }
 
@@ -72,8 +74,8 @@ impl Bathroom {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = Bathroom;
-    fn solve_1(input: &Bathroom) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = Bathroom;
+    fn solve_1(input: &Bathroom) -> MyResult<impl Debug + use<>> {
         let bathroom_size = input.size();
         let new_locations = input.robots.iter().map(|r| r.predict(100, bathroom_size)).collect::<Vec<_>>();
         let mut quadrant_counts = [0;4];
@@ -84,7 +86,7 @@ impl DaySolution for Solution {
         }
         Ok(quadrant_counts.into_iter().product::<i64>())
     }
-    fn solve_2(input: &Bathroom) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &Bathroom) -> MyResult<impl Debug + use<>> {
         let bathroom_size = input.size();
 
         for i in 0..10_000 {
@@ -1,20 +1,21 @@
-use core::panic;
 use std::{collections::HashSet, fmt::Debug};
 
-use aoc_utils::{formatted_struct, Chars, DaySolution, MyResult};
+use aoc_utils::{char_enum, formatted_struct, Chars, DaySolution, Grid, MyResult, Point};
 
-formatted_struct! {
-    #[derive(Debug)]
-    pub struct Map {
-        #[separated_by="\n"]
-        rows: Vec<Chars>,
+char_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Tile {
+        Wall = '#',
+        Crate = 'O',
+        Empty = '.',
+        Robot = '@',
     }
 }
 
 formatted_struct! {
     #[derive(Debug)]
     pub struct InputFormat {
-        map: Map,
+        map: Grid<Tile>,
         "\n\n",
         instructions: Chars,
     }
@@ -24,34 +25,20 @@ pub struct Solution;
 
 #[derive(Debug)]
 struct State {
-    map: Vec<Vec<char>>,
-    current_pos: (usize, usize),
+    map: Grid<Tile>,
+    current_pos: Point,
 }
 
-impl From<&Map> for State {
-    fn from(value: &Map) -> Self {
-        let mut map = value
-            .rows
-            .iter()
-            .map(|Chars(row)| row.clone())
-            .collect::<Vec<_>>();
-        let current_pos = map
-            .iter()
-            .enumerate()
-            .filter_map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter_map(|(j, &c)| if c == '@' { Some((i, j)) } else { None })
-                    .next()
-            })
-            .next()
-            .expect("must have @");
-        map[current_pos.0][current_pos.1] = '.';
+impl From<&Grid<Tile>> for State {
+    fn from(value: &Grid<Tile>) -> Self {
+        let mut map = value.clone();
+        let current_pos = map.find(&Tile::Robot).expect("must have @");
+        map[current_pos] = Tile::Empty;
         State { map, current_pos }
     }
 }
 
-fn decode_dir(char_direction: char) -> (isize, isize) {
+fn decode_dir(char_direction: char) -> Point {
     match char_direction {
         '<' => (0, -1),
         '>' => (0, 1),
@@ -60,46 +47,36 @@ fn decode_dir(char_direction: char) -> (isize, isize) {
         _ => panic!("unknown direction '{}'", char_direction),
     }
 }
+
+fn step(at: Point, direction: Point) -> Point {
+    (at.0 + direction.0, at.1 + direction.1)
+}
+
 impl State {
-    fn push(&mut self, at: (usize, usize), direction: (isize, isize)) {
-        if self.map[at.0][at.1] != 'O' {
+    fn push(&mut self, at: Point, direction: Point) {
+        if self.map[at] != Tile::Crate {
             return;
         }
-        let next_space = (
-            (at.0 as isize + direction.0) as usize,
-            (at.1 as isize + direction.1) as usize,
-        );
+        let next_space = step(at, direction);
         self.push(next_space, direction);
-        if self.map[next_space.0][next_space.1] != '.' {
+        if self.map[next_space] != Tile::Empty {
             return;
         }
-        self.map[next_space.0][next_space.1] = self.map[at.0][at.1];
-        self.map[at.0][at.1] = '.';
+        self.map[next_space] = self.map[at];
+        self.map[at] = Tile::Empty;
     }
-    fn move_robot_with_dir(&mut self, direction: (isize, isize)) {
-        let next_space = (
-            (self.current_pos.0 as isize + direction.0) as usize,
-            (self.current_pos.1 as isize + direction.1) as usize,
-        );
+    fn move_robot_with_dir(&mut self, direction: Point) {
+        let next_space = step(self.current_pos, direction);
         self.push(next_space, direction);
-        if self.map[next_space.0][next_space.1] == '.' {
+        if self.map[next_space] == Tile::Empty {
             self.current_pos = next_space;
         }
     }
     fn move_robot(&mut self, char_direction: char) {
         self.move_robot_with_dir(decode_dir(char_direction));
     }
-    fn gps_score(&self) -> usize {
-        self.map
-            .iter()
-            .enumerate()
-            .map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(j, &c)| if c == 'O' { 100 * i + j } else { 0 })
-                    .sum::<usize>()
-            })
-            .sum()
+    fn gps_score(&self) -> isize {
+        self.map.find_all(&Tile::Crate).map(|(i, j)| 100 * i + j).sum()
     }
 }
 
@@ -109,61 +86,56 @@ enum Object {
     Wall,
     Crate { id: usize },
 }
-fn all_crate_pos(left_pos: (usize, usize)) -> impl IntoIterator<Item = (usize, usize)> {
+fn all_crate_pos(left_pos: Point) -> impl IntoIterator<Item = Point> {
     [left_pos, (left_pos.0, left_pos.1 + 1)]
 }
 
 struct Map2State {
-    map: Vec<Vec<Object>>,
-    crates_left_pos: Vec<(usize, usize)>,
-    robot_pos: (usize, usize),
+    map: Grid<Object>,
+    crates_left_pos: Vec<Point>,
+    robot_pos: Point,
 }
 
-impl From<&Map> for Map2State {
-    fn from(value: &Map) -> Self {
-        let height = value.rows.len();
-        let width = value.rows[0].0.len() * 2;
-        let mut map = vec![vec![Object::Empty; width]; height];
-        let mut crates_left_pos = Vec::new();
-        let mut robot_pos = None;
-        for (row_no, row) in value.rows.iter().enumerate() {
-            for (col_no, object_char) in row.0.iter().enumerate() {
-                let col_no = col_no * 2;
-                match *object_char {
-                    '@' => {
-                        assert!(robot_pos.is_none());
-                        robot_pos = Some((row_no, col_no));
-                    }
-                    '#' => {
-                        map[row_no][col_no..(col_no + 2)].fill(Object::Wall);
-                    }
-                    'O' => {
-                        map[row_no][col_no..(col_no + 2)].fill(Object::Crate {
-                            id: crates_left_pos.len(),
-                        });
-                        crates_left_pos.push((row_no, col_no));
-                    }
-                    '.' => (),
-                    _ => panic!(),
-                };
-            }
-        }
+impl Map2State {
+    fn crate_space(&mut self, (i, j): Point) -> &mut [Object] {
+        &mut self.map.row_mut(i as usize)[j as usize..(j as usize + 2)]
+    }
+}
 
-        Map2State {
-            map,
-            crates_left_pos,
-            robot_pos: robot_pos.unwrap(),
+impl From<&Grid<Tile>> for Map2State {
+    fn from(value: &Grid<Tile>) -> Self {
+        let mut state = Map2State {
+            map: Grid::new(value.height(), value.width() * 2, Object::Empty),
+            crates_left_pos: Vec::new(),
+            robot_pos: (0, 0),
+        };
+        let mut robot_pos = None;
+        for ((row_no, col_no), tile) in value.iter() {
+            let left_pos = (row_no, col_no * 2);
+            match tile {
+                Tile::Robot => {
+                    assert!(robot_pos.is_none());
+                    robot_pos = Some(left_pos);
+                }
+                Tile::Wall => {
+                    state.crate_space(left_pos).fill(Object::Wall);
+                }
+                Tile::Crate => {
+                    let id = state.crates_left_pos.len();
+                    state.crate_space(left_pos).fill(Object::Crate { id });
+                    state.crates_left_pos.push(left_pos);
+                }
+                Tile::Empty => (),
+            };
         }
+        state.robot_pos = robot_pos.unwrap();
+        state
     }
 }
 
 impl Map2State {
-    fn push_instructions(
-        &self,
-        at: (usize, usize),
-        direction: (isize, isize),
-    ) -> Option<HashSet<(usize, (usize, usize))>> {
-        let crate_id = match self.map[at.0][at.1] {
+    fn push_instructions(&self, at: Point, direction: Point) -> Option<HashSet<(usize, Point)>> {
+        let crate_id = match self.map[at] {
             Object::Crate { id } => id,
             Object::Empty => return Some(HashSet::new()),
             Object::Wall => return None,
@@ -173,12 +145,7 @@ impl Map2State {
             .collect::<Vec<_>>();
         let pushed_pos = crate_pos
             .iter()
-            .map(|&p| {
-                (
-                    (p.0 as isize + direction.0) as usize,
-                    (p.1 as isize + direction.1) as usize,
-                )
-            })
+            .map(|&p| step(p, direction))
             .filter(|p| !crate_pos.contains(p))
             .collect::<Vec<_>>();
         let mut result = HashSet::new();
@@ -187,22 +154,13 @@ impl Map2State {
         }
         let current_pos = self.crates_left_pos[crate_id];
 
-        result.insert((
-            crate_id,
-            (
-                (current_pos.0 as isize + direction.0) as usize,
-                (current_pos.1 as isize + direction.1) as usize,
-            ),
-        ));
+        result.insert((crate_id, step(current_pos, direction)));
 
         Some(result)
     }
-    fn move_robot(&mut self, direction: (isize, isize)) {
-        assert_eq!(self.map[self.robot_pos.0][self.robot_pos.1], Object::Empty);
-        let next_space = (
-            (self.robot_pos.0 as isize + direction.0) as usize,
-            (self.robot_pos.1 as isize + direction.1) as usize,
-        );
+    fn move_robot(&mut self, direction: Point) {
+        assert_eq!(self.map[self.robot_pos], Object::Empty);
+        let next_space = step(self.robot_pos, direction);
         let instructions = match self.push_instructions(next_space, direction) {
             None => return,
             Some(x) => x,
@@ -217,7 +175,7 @@ impl Map2State {
         );
         for &(crate_id, _) in &instructions {
             let old_left_pos = self.crates_left_pos[crate_id];
-            let space = &mut self.map[old_left_pos.0][old_left_pos.1..(old_left_pos.1 + 2)];
+            let space = self.crate_space(old_left_pos);
             assert_eq!(
                 *space,
                 [
@@ -229,21 +187,21 @@ impl Map2State {
         }
 
         for &(crate_id, new_left_pos) in &instructions {
-            let space = &mut self.map[new_left_pos.0][new_left_pos.1..(new_left_pos.1 + 2)];
+            let space = self.crate_space(new_left_pos);
             assert_eq!(*space, [Object::Empty, Object::Empty]);
             space.fill(Object::Crate { id: crate_id });
             self.crates_left_pos[crate_id] = new_left_pos;
         }
 
-        assert_eq!(self.map[self.robot_pos.0][self.robot_pos.1], Object::Empty);
-        assert_eq!(self.map[next_space.0][next_space.1], Object::Empty);
+        assert_eq!(self.map[self.robot_pos], Object::Empty);
+        assert_eq!(self.map[next_space], Object::Empty);
         self.robot_pos = next_space;
     }
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut state = State::from(&input.map);
         for &char in &input.instructions.0 {
             if char == '\n' {
@@ -254,7 +212,7 @@ impl DaySolution for Solution {
         }
         Ok(state.gps_score())
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut state = Map2State::from(&input.map);
         for &char in &input.instructions.0 {
             if char == '\n' {
@@ -267,7 +225,7 @@ impl DaySolution for Solution {
             .crates_left_pos
             .iter()
             .map(|&(i, j)| 100 * i + j)
-            .sum::<usize>();
+            .sum::<isize>();
         Ok(gps_score)
     }
     fn preferred_sample_input() -> i32 {
@@ -1,19 +1,25 @@
-use std::{
-    collections::HashSet,
-    fmt::Debug,
-};
+use std::{collections::HashSet, fmt::Debug};
 
 use aoc_utils::{
-    formatted_struct,
+    char_enum, formatted_struct,
     graph::{Edge, Graph},
-    Chars, DaySolution, MyResult,
+    DaySolution, Grid, MyResult, Point,
 };
 
+char_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Tile {
+        Wall = '#',
+        Track = '.',
+        Start = 'S',
+        End = 'E',
+    }
+}
+
 formatted_struct! {
     #[derive(Debug)]
     pub struct InputFormat {
-        #[separated_by="\n"]
-        rows: Vec<Chars>,
+        grid: Grid<Tile>,
     }
 }
 
@@ -25,63 +31,42 @@ enum Orientation {
 
 use Orientation::*;
 impl InputFormat {
-    #[allow(clippy::type_complexity)]
-    fn read_graph(
-        &self,
-    ) -> (
-        Graph<((usize, usize), Orientation), i32>,
-        (usize, usize),
-        (usize, usize),
-    ) {
+    fn read_graph(&self) -> (Graph<(Point, Orientation), i32>, Point, Point) {
+        let grid = &self.grid;
         let mut edges = Vec::new();
-        let mut start_pos = None;
-        let mut end_pos = None;
-        for i in 1..(self.rows.len() - 1) {
-            for j in 1..self.rows[i].0.len() - 1 {
-                let current_char = self.rows[i].0[j];
-                let current_pos = Some((i, j));
-                match current_char {
-                    'S' => {
-                        assert!(start_pos.is_none());
-                        start_pos = current_pos;
-                    }
-                    'E' => {
-                        assert!(end_pos.is_none());
-                        end_pos = current_pos;
-                    }
-                    '#' => continue,
-                    _ => {}
-                };
+        for (current_pos @ (i, j), &tile) in grid.iter() {
+            if tile == Tile::Wall {
+                continue;
+            }
+            edges.extend([
+                ((current_pos, Vertical), (current_pos, Horizontal), 1000),
+                ((current_pos, Horizontal), (current_pos, Vertical), 1000),
+            ]);
+            if grid.get((i, j + 1)).is_some_and(|&tile| tile != Tile::Wall) {
                 edges.extend([
-                    (((i, j), Vertical), ((i, j), Horizontal), 1000),
-                    (((i, j), Horizontal), ((i, j), Vertical), 1000),
+                    ((current_pos, Horizontal), ((i, j + 1), Horizontal), 1),
+                    (((i, j + 1), Horizontal), (current_pos, Horizontal), 1),
+                ]);
+            }
+            if grid.get((i + 1, j)).is_some_and(|&tile| tile != Tile::Wall) {
+                edges.extend([
+                    ((current_pos, Vertical), ((i + 1, j), Vertical), 1),
+                    (((i + 1, j), Vertical), (current_pos, Vertical), 1),
                 ]);
-                if self.rows[i].0[j + 1] != '#' {
-                    edges.extend([
-                        (((i, j), Horizontal), ((i, j + 1), Horizontal), 1),
-                        (((i, j + 1), Horizontal), ((i, j), Horizontal), 1),
-                    ]);
-                }
-                if self.rows[i + 1].0[j] != '#' {
-                    edges.extend([
-                        (((i, j), Vertical), ((i + 1, j), Vertical), 1),
-                        (((i + 1, j), Vertical), ((i, j), Vertical), 1),
-                    ]);
-                }
             }
         }
         (
             Graph::from_edges(edges),
-            start_pos.unwrap(),
-            end_pos.unwrap(),
+            grid.find(&Tile::Start).unwrap(),
+            grid.find(&Tile::End).unwrap(),
         )
     }
 }
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let (graph, start, end) = input.read_graph();
         let distance_function =
             graph.shortest_paths((start, Horizontal), |&(current, _)| current == end);
@@ -92,7 +77,7 @@ impl DaySolution for Solution {
             .unwrap();
         Ok(results)
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let (graph, start, end) = input.read_graph();
         let distance_function = graph.shortest_paths((start, Horizontal), |_| false);
         let best_path_length = [Horizontal, Vertical]
@@ -13,10 +13,27 @@ formatted_struct! {
         initial_c: i64,
         "\n\nProgram: ",
         #[separated_by=","]
+        #[validate(with = check_program)]
         program: Vec<i64>,
     }
 }
 
+// Every instruction needs its operand, and `combo_value` has no value for 7.
+fn check_program(program: &[i64]) -> Result<(), String> {
+    if program.len() % 2 == 1 {
+        return Err("the last instruction has no operand".to_string());
+    }
+    for (i, &value) in program.iter().enumerate() {
+        if !(0..8).contains(&value) {
+            return Err(format!("{} at {} is not a 3-bit number", value, i));
+        }
+        if i % 2 == 1 && value == 7 && matches!(program[i - 1], 0 | 2 | 5 | 6 | 7) {
+            return Err(format!("the combo operand at {} is 7", i));
+        }
+    }
+    Ok(())
+}
+
 struct State<'a> {
     a: i64,
     b: i64,
@@ -92,8 +109,8 @@ impl<'s> State<'s> {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut state = State {
             a: input.initial_a,
             b: input.initial_b,
@@ -1,24 +1,16 @@
 use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
+    collections::{BinaryHeap, HashMap},
     fmt::Debug,
 };
 
-use aoc_utils::{formatted_struct, DaySolution, MyResult};
-
-formatted_struct! {
-    #[derive(Debug)]
-    pub struct MemoryLocation {
-        x:i32,
-        ",",
-        y:i32,
-    }
-}
+use aoc_utils::{formatted_struct, DaySolution, Grid, MyResult, Point};
 
 formatted_struct! {
     #[derive(Debug)]
     pub struct InputFormat {
         #[separated_by="\n"]
-        locations: Vec<MemoryLocation>,
+        #[tuple_sep=","]
+        locations: Vec<(i32, i32)>,
     }
 }
 
@@ -26,13 +18,10 @@ pub struct Solution;
 
 #[derive(Debug)]
 struct Graph {
-    size: (i32, i32),
-    corruptions: HashSet<(i32, i32)>,
+    corruptions: Grid<bool>,
 }
 
-const DELTAS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-
-fn size_n_corruptions(input: &InputFormat) -> ((i32, i32), usize) {
+fn size_n_corruptions(input: &InputFormat) -> ((usize, usize), usize) {
     match input.locations.len() {
         25 => ((7, 7), 12),
         3450 => ((71, 71), 1024),
@@ -42,29 +31,25 @@ fn size_n_corruptions(input: &InputFormat) -> ((i32, i32), usize) {
 
 impl Graph {
     fn new(input: &InputFormat, n_corruptions: usize) -> Graph {
-        let (size, _) = size_n_corruptions(input);
-        let corruptions = input.locations[..n_corruptions]
-            .iter()
-            .map(|MemoryLocation { x, y }| (*x, *y))
-            .collect::<HashSet<_>>();
-        Graph { size, corruptions }
-    }
-    fn adj_locations(&self, location: (i32, i32)) -> Vec<(i32, i32)> {
-        let mut result = vec![];
-        let (n, m) = self.size;
-        for &(di, dj) in &DELTAS {
-            let i = location.0 + di;
-            let j = location.1 + dj;
-            if !(0..n).contains(&i) || !(0..m).contains(&j) || self.corruptions.contains(&(i, j)) {
-                continue;
-            }
-            result.push((i, j));
+        let ((n, m), _) = size_n_corruptions(input);
+        let mut corruptions = Grid::new(n, m, false);
+        for (x, y) in &input.locations[..n_corruptions] {
+            corruptions[(*x as isize, *y as isize)] = true;
         }
-        result
+        Graph { corruptions }
+    }
+    fn adj_locations(&self, location: Point) -> impl Iterator<Item = Point> + '_ {
+        self.corruptions
+            .neighbors_4(location)
+            .filter(|&adj_location| !self.corruptions[adj_location])
     }
     fn go_to_target(&self) -> Option<i32> {
-        let mut state = AStarState::new(self.size);
-        let target = (self.size.0 - 1, self.size.1 - 1);
+        let size = (
+            self.corruptions.height() as isize,
+            self.corruptions.width() as isize,
+        );
+        let mut state = AStarState::new(size);
+        let target = (size.0 - 1, size.1 - 1);
         state.push((0, 0), 0);
 
         while let Some((location, n_steps)) = state.pop() {
@@ -80,23 +65,23 @@ impl Graph {
 }
 
 struct AStarState {
-    size: (i32, i32),
-    state_queue: BinaryHeap<(i32, i32, (i32, i32))>,
-    min_steps_per_state: HashMap<(i32, i32), i32>,
+    size: Point,
+    state_queue: BinaryHeap<(i32, i32, Point)>,
+    min_steps_per_state: HashMap<Point, i32>,
 }
 
 impl AStarState {
-    fn new(size: (i32, i32)) -> AStarState {
+    fn new(size: Point) -> AStarState {
         AStarState {
             size,
             state_queue: BinaryHeap::new(),
             min_steps_per_state: HashMap::new(),
         }
     }
-    fn optimistic_remaining(&self, location: (i32, i32), n_steps: i32) -> i32 {
-        self.size.0 - location.0 + self.size.1 - location.1 + n_steps
+    fn optimistic_remaining(&self, location: Point, n_steps: i32) -> i32 {
+        (self.size.0 - location.0 + self.size.1 - location.1) as i32 + n_steps
     }
-    fn push(&mut self, location: (i32, i32), n_steps: i32) {
+    fn push(&mut self, location: Point, n_steps: i32) {
         if let Some(&current_distance) = self.min_steps_per_state.get(&location) {
             if current_distance <= n_steps {
                 return;
@@ -106,7 +91,7 @@ impl AStarState {
         self.state_queue.push((-remaining, -n_steps, location));
         self.min_steps_per_state.insert(location, n_steps);
     }
-    fn pop(&mut self) -> Option<((i32, i32), i32)> {
+    fn pop(&mut self) -> Option<(Point, i32)> {
         while let Some((_, negative_n_steps, location)) = self.state_queue.pop() {
             let n_steps = -negative_n_steps;
             let min_steps = self.min_steps_per_state.get(&location).unwrap();
@@ -120,13 +105,13 @@ impl AStarState {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let (_, n_corruptions) = size_n_corruptions(input);
         let graph = Graph::new(input, n_corruptions);
         Ok(graph.go_to_target().unwrap())
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut max_solvable = 0;
         let mut min_unsolvable = input.locations.len() - 1;
         while max_solvable + 1 < min_unsolvable {
@@ -139,7 +124,6 @@ impl DaySolution for Solution {
                 min_unsolvable = mid;
             }
         }
-        let breaking_byte = &input.locations[min_unsolvable];
-        Ok((breaking_byte.x, breaking_byte.y))
+        Ok(input.locations[min_unsolvable])
     }
 }
@@ -1,21 +1,21 @@
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug};
 
 use aoc_utils::{formatted_struct, DaySolution, MyResult};
 
 formatted_struct! {
     #[derive(Debug)]
-    pub struct InputFormat {
+    pub struct InputFormat<'a> {
         #[separated_by=", "]
-        towels: Vec<String>,
+        towels: HashSet<&'a str>,
         "\n\n",
         #[separated_by="\n"]
-        designs: Vec<String>,
+        designs: Vec<&'a str>,
     }
 }
 
 pub struct Solution;
 
-fn count_possibilities(design: &str, towels: &[String]) -> i64 {
+fn count_possibilities(design: &str, towels: &HashSet<&str>) -> i64 {
     let mut prefix_possible = Vec::with_capacity(design.len() + 1);
     prefix_possible.push(1);
     for prefix_len in 1..=design.len() {
@@ -38,15 +38,15 @@ fn count_possibilities(design: &str, towels: &[String]) -> i64 {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat<'a>;
+    fn solve_1(input: &InputFormat<'_>) -> MyResult<impl Debug + use<>> {
         Ok(input
             .designs
             .iter()
             .map(|d| (count_possibilities(d, &input.towels) > 0) as i32)
             .sum::<i32>())
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat<'_>) -> MyResult<impl Debug + use<>> {
         Ok(input
             .designs
             .iter()
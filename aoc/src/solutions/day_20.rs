@@ -3,13 +3,23 @@ use std::{
     fmt::Debug,
 };
 
-use aoc_utils::{formatted_struct, graph::Graph, Chars, DaySolution, MyResult};
+use aoc_utils::{char_enum, formatted_struct, graph::Graph, DaySolution, Grid, MyResult, Point};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+char_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Tile {
+        Wall = '#',
+        Track = '.',
+        Start = 'S',
+        End = 'E',
+    }
+}
 
 formatted_struct! {
     #[derive(Debug)]
     pub struct InputFormat {
-        #[separated_by="\n"]
-        rows: Vec<Chars>,
+        grid: Grid<Tile>,
     }
 }
 
@@ -18,51 +28,30 @@ pub struct Solution;
 const DELTAS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
 
 impl InputFormat {
-    #[allow(clippy::type_complexity)]
-    fn read_graph(&self) -> (Graph<(i32, i32), ()>, (i32, i32), (i32, i32)) {
+    fn read_graph(&self) -> (Graph<Point, ()>, Point, Point) {
+        let grid = &self.grid;
         let mut edges = HashSet::new();
-        let mut start_pos = None;
-        let mut end_pos = None;
-        for i in 1..(self.rows.len() - 1) {
-            for j in 1..self.rows[i].0.len() - 1 {
-                let current_char = self.rows[i].0[j];
-                let current_pos = Some((i as i32, j as i32));
-                match current_char {
-                    'S' => {
-                        assert!(start_pos.is_none());
-                        start_pos = current_pos;
-                    }
-                    'E' => {
-                        assert!(end_pos.is_none());
-                        end_pos = current_pos;
-                    }
-                    '#' => continue,
-                    _ => {}
-                };
-                for (di, dj) in DELTAS {
-                    let ci = di + i as i32;
-                    let cj = dj + j as i32;
-                    if self.rows[ci as usize].0[cj as usize] != '.' {
-                        continue;
-                    }
-                    edges.extend([
-                        ((i as i32, j as i32), (ci, cj)),
-                        ((ci, cj), (i as i32, j as i32)),
-                    ]);
+        for (current_pos, &tile) in grid.iter() {
+            if tile == Tile::Wall {
+                continue;
+            }
+            for adjacent in grid.neighbors_4(current_pos) {
+                if grid[adjacent] != Tile::Track {
+                    continue;
                 }
+                edges.extend([(current_pos, adjacent), (adjacent, current_pos)]);
             }
         }
         (
             Graph::from_edges(edges),
-            start_pos.unwrap(),
-            end_pos.unwrap(),
+            grid.find(&Tile::Start).unwrap(),
+            grid.find(&Tile::End).unwrap(),
         )
     }
     fn count_threshold(&self) -> [i32; 2] {
-        match self.rows.len() {
+        match self.grid.height() {
             15 => [1, 50],
-            141 => [100, 100],
-            _ => panic!("{}", self.rows.len()),
+            _ => [100, 100],
         }
     }
     fn generic_solve(&self, cheat_threshold: i32, count_threshold: i32) -> (i32, Vec<(i32, i32)>) {
@@ -72,7 +61,7 @@ impl InputFormat {
         let no_cheating_distance = distance_from_start(end_pos).unwrap();
 
         let mut cheat_count = HashMap::new();
-        let mut add_cheat_distance = |cheat_start: (i32, i32), cheat_end: (i32, i32)| {
+        let mut add_cheat_distance = |cheat_start: Point, cheat_end: Point| {
             let cheat_duration =
                 (cheat_start.0.abs_diff(cheat_end.0) + cheat_start.1.abs_diff(cheat_end.1)) as i32;
             if cheat_duration > cheat_threshold {
@@ -104,11 +93,55 @@ impl InputFormat {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(input.generic_solve(2, input.count_threshold()[0]))
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(input.generic_solve(20, input.count_threshold()[1]))
     }
+    fn generate(seed: u64, size: usize) -> MyResult<String> {
+        // The track is the path to the deepest cell of a random spanning tree over the odd
+        // coordinates, so it has no forks, just like the real inputs.
+        let mut rng = StdRng::seed_from_u64(seed);
+        let size = size.max(5) | 1;
+        let n_cells = (size / 2) as i32;
+        let start = (rng.gen_range(0..n_cells), rng.gen_range(0..n_cells));
+        let mut parents = HashMap::from([(start, start)]);
+        let mut stack = vec![(start, 0)];
+        let mut deepest = (start, 0);
+        while let Some(&(cell, depth)) = stack.last() {
+            let mut unvisited = DELTAS
+                .iter()
+                .map(|(di, dj)| (cell.0 + di, cell.1 + dj))
+                .filter(|(i, j)| (0..n_cells).contains(i) && (0..n_cells).contains(j))
+                .filter(|next| !parents.contains_key(next))
+                .collect::<Vec<_>>();
+            unvisited.shuffle(&mut rng);
+            let Some(&next) = unvisited.first() else {
+                stack.pop();
+                continue;
+            };
+            parents.insert(next, cell);
+            stack.push((next, depth + 1));
+            if depth + 1 > deepest.1 {
+                deepest = (next, depth + 1);
+            }
+        }
+
+        let mut grid = Grid::new(size, size, Tile::Wall);
+        let to_grid = |(i, j): (i32, i32)| ((2 * i + 1) as isize, (2 * j + 1) as isize);
+        let mut cell = deepest.0;
+        while cell != start {
+            let parent = parents[&cell];
+            let (ci, cj) = to_grid(cell);
+            let (pi, pj) = to_grid(parent);
+            grid[(ci, cj)] = Tile::Track;
+            grid[((ci + pi) / 2, (cj + pj) / 2)] = Tile::Track;
+            cell = parent;
+        }
+        grid[to_grid(start)] = Tile::Start;
+        grid[to_grid(deepest.0)] = Tile::End;
+        Ok(grid.to_string())
+    }
 }
@@ -141,7 +141,7 @@ fn cached_translate_path(
         .map(|segment_counts| {
             let mut result = HashMap::new();
             for (segment, count) in segment_counts {
-                let translated = translate_path(&[segment.clone()], paths_to_buttons);
+                let translated = translate_path(std::slice::from_ref(segment), paths_to_buttons);
                 assert_eq!(translated.len(), 1);
                 for new_segment in translated[0].split_inclusive("A") {
                     *result.entry(new_segment.to_string()).or_insert(0) += count;
@@ -237,11 +237,11 @@ fn dpad_solve(
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(general_solve(input, 2))
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         Ok(general_solve(input, 25))
     }
     fn preferred_sample_input() -> i32 {
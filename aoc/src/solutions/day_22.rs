@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use aoc_utils::{formatted_struct, DaySolution, MyResult};
+use aoc_utils::{formatted_struct, Cancelled, DaySolution, MyResult, Progress};
 use rayon::prelude::*;
 
 formatted_struct! {
@@ -49,8 +49,8 @@ fn sell_price(delta_sequence: &[i64; 4], prices: &[i64], deltas: &[i64]) -> i64
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut sum = 0;
         for &initial_secret in &input.initial_secrets {
             let mut x = initial_secret;
@@ -61,7 +61,7 @@ impl DaySolution for Solution {
         }
         Ok(sum)
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + use<>> {
         let mut price_sequences = Vec::with_capacity(input.initial_secrets.len());
         for &initial_secret in &input.initial_secrets {
             let mut x = initial_secret;
@@ -84,17 +84,19 @@ impl DaySolution for Solution {
             })
             .collect::<Vec<_>>();
         let delta_sequences = make_delta_sequences();
+        let progress = Progress::new(delta_sequences.len());
         let best_price = delta_sequences
             .par_iter()
-            .map(|delta_sequence| {
-                price_sequences
+            .map(|delta_sequence| -> Result<i64, Cancelled> {
+                let total_price = price_sequences
                     .iter()
                     .zip(&price_deltas)
                     .map(|(prices, deltas)| sell_price(delta_sequence, prices, deltas))
-                    .sum::<i64>()
+                    .sum::<i64>();
+                progress.inc()?;
+                Ok(total_price)
             })
-            .max()
-            .unwrap();
+            .try_reduce(|| 0, |lh, rh| Ok(lh.max(rh)))?;
         Ok(best_price)
     }
     fn preferred_sample_input() -> i32 {
@@ -7,31 +7,28 @@ use aoc_utils::{formatted_struct, DaySolution, MyResult};
 
 formatted_struct! {
     #[derive(Debug)]
-    pub struct Connection {
-        from: String,
+    pub struct Connection<'a> {
+        from: &'a str,
         "-",
-        to: String,
+        to: &'a str,
     }
 }
 
 formatted_struct! {
     #[derive(Debug)]
-    pub struct InputFormat {
+    pub struct InputFormat<'a> {
         #[separated_by="\n"]
-        connections: Vec<Connection>,
+        connections: Vec<Connection<'a>>,
     }
 }
 
 pub struct Solution;
 
-impl InputFormat {
+impl<'i> InputFormat<'i> {
     fn all_edges(&self) -> impl Iterator<Item = (&str, &str)> {
-        self.connections.iter().flat_map(|c| {
-            [
-                (c.from.as_str(), c.to.as_str()),
-                (c.to.as_str(), c.from.as_str()),
-            ]
-        })
+        self.connections
+            .iter()
+            .flat_map(|c| [(c.from, c.to), (c.to, c.from)])
     }
     fn compute_adj(&self) -> HashMap<&str, HashSet<&str>> {
         let mut adj = HashMap::new();
@@ -63,8 +60,8 @@ impl InputFormat {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    type InputFormat<'a> = InputFormat<'a>;
+    fn solve_1(input: &InputFormat<'_>) -> MyResult<impl Debug + use<>> {
         let adj = input.compute_adj();
         Ok(input
             .clique_3(&adj)
@@ -72,7 +69,7 @@ impl DaySolution for Solution {
             .filter(|&triplet| triplet.iter().any(|&u| u.starts_with('t')))
             .count())
     }
-    fn solve_2(input: &InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat<'_>) -> MyResult<impl Debug + use<>> {
         let adj = input.compute_adj();
         let cliques = Vec::from_iter(input.clique_3(&adj));
         let mut cliques = Vec::from_iter(cliques);
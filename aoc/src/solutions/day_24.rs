@@ -6,11 +6,11 @@ use std::{
 use aoc_utils::{formatted_struct, make_recursive_fn, DaySolution, MyResult};
 
 formatted_struct! {
-    #[derive(Debug)]
-    pub struct Param {
-        key: String,
-        ": ",
-        value: i32,
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Op {
+        And = "AND",
+        Or = "OR",
+        Xor = "XOR",
     }
 }
 
@@ -19,7 +19,7 @@ formatted_struct! {
     pub struct Gate {
         lh: String,
         " ",
-        op: String,
+        op: Op,
         " ",
         rh: String,
         " -> ",
@@ -29,9 +29,10 @@ formatted_struct! {
 
 formatted_struct! {
     #[derive(Debug)]
-    pub struct InputFormat {
+    pub struct InputFormat<'a> {
         #[separated_by="\n"]
-        params: Vec<Param>,
+        #[key_value_sep=": "]
+        params: HashMap<&'a str, i32>,
         "\n\n",
         #[separated_by="\n"]
         gates: Vec<Gate>,
@@ -41,20 +42,16 @@ formatted_struct! {
 pub struct Solution;
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
-        let params = input
-            .params
-            .iter()
-            .map(|p| (p.key.as_str(), p.value))
-            .collect::<HashMap<_, _>>();
+    type InputFormat<'a> = InputFormat<'a>;
+    fn solve_1(input: &InputFormat<'_>) -> MyResult<impl Debug + use<>> {
+        let params = &input.params;
         let gates = input
             .gates
             .iter()
             .map(|g| {
                 (
                     g.output.as_str(),
-                    (g.lh.as_str(), g.op.as_str(), g.rh.as_str()),
+                    (g.lh.as_str(), g.op, g.rh.as_str()),
                 )
             })
             .collect::<HashMap<_, _>>();
@@ -66,10 +63,9 @@ impl DaySolution for Solution {
             let lh = eval(lh_name.to_string());
             let rh = eval(rh_name.to_string());
             match op {
-                "AND" => lh & rh,
-                "XOR" => lh ^ rh,
-                "OR" => lh | rh,
-                _ => unimplemented!(),
+                Op::And => lh & rh,
+                Op::Xor => lh ^ rh,
+                Op::Or => lh | rh,
             }
         });
         let mut z_gates = gates
@@ -85,7 +81,7 @@ impl DaySolution for Solution {
             .join("");
         Ok(i64::from_str_radix(&bin_result, 2))
     }
-    fn solve_2(input: &Self::InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(input: &InputFormat<'_>) -> MyResult<impl Debug + use<>> {
         let mut current_gates = input.gates.clone();
         let mut swaps = Vec::new();
         let add_swap =
@@ -115,6 +111,9 @@ impl DaySolution for Solution {
                 ($g:ident, param=$v:expr) => {
                     [$g.lh.as_str(), $g.rh.as_str()].contains(&$v)
                 };
+                ($g:ident, op=$v:expr) => {
+                    ($g.op == $v)
+                };
                 ($g:ident, $k:ident=$v:expr) => {
                     ($g.$k.as_str() == $v)
                 };
@@ -133,7 +132,7 @@ impl DaySolution for Solution {
 
             assert_eq!(
                 Vec::from_iter(
-                    query_gates!(select output where param = "x00", param="y00", op = "XOR")
+                    query_gates!(select output where param = "x00", param="y00", op = Op::Xor)
                 ),
                 vec!["z00"]
             );
@@ -145,15 +144,15 @@ impl DaySolution for Solution {
                 let z = format!("z{:02}", i);
 
                 let xor_inner_1 = [
-                    query_gates!(select output where param=x, op="XOR"),
-                    query_gates!(select output where param=y, op="XOR"),
+                    query_gates!(select output where param=x, op=Op::Xor),
+                    query_gates!(select output where param=y, op=Op::Xor),
                 ]
                 .into_iter()
                 .flatten()
                 .collect::<HashSet<_>>();
-                let xor_inner_2 = query_gates!(select lh, rh where output=z, op="XOR");
+                let xor_inner_2 = query_gates!(select lh, rh where output=z, op=Op::Xor);
                 if xor_inner_1.len() == 1 {
-                    let xor_inner_2_candidates = query_gates!(select output where param=xor_inner_1.iter().next().unwrap(), op="XOR");
+                    let xor_inner_2_candidates = query_gates!(select output where param=xor_inner_1.iter().next().unwrap(), op=Op::Xor);
 
                     if xor_inner_2.len() != 2 {
                         assert_eq!(
@@ -175,12 +174,13 @@ impl DaySolution for Solution {
                         let diff = xor_inner_2.difference(&xor_inner_1).collect::<Vec<_>>();
                         if diff.len() != 1 {
                             let xor_inner_2 = Vec::from_iter(xor_inner_2.clone());
-                            let op0 = *query_gates!(select op where output=xor_inner_2[0])
+                            let op0 = current_gates
                                 .iter()
-                                .next()
-                                .unwrap();
-                            assert!(["OR", "AND"].contains(&op0));
-                            let not_carry = if op0 == "OR" {
+                                .find(|g| g.output == xor_inner_2[0])
+                                .unwrap()
+                                .op;
+                            assert!([Op::Or, Op::And].contains(&op0));
+                            let not_carry = if op0 == Op::Or {
                                 xor_inner_2[1]
                             } else {
                                 xor_inner_2[0]
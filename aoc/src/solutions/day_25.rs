@@ -1,37 +1,25 @@
 use std::fmt::Debug;
 
-use aoc_utils::{formatted_struct, Chars, DaySolution, MyResult};
+use aoc_utils::{formatted_struct, Cancelled, DaySolution, Grid, MyResult, Progress};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-formatted_struct! {
-    #[derive(Debug)]
-    pub struct Schematic {
-        #[separated_by="\n"]
-        rows: Vec<Chars>,
-    }
-}
-
 formatted_struct! {
     #[derive(Debug)]
     pub struct InputFormat {
         #[separated_by="\n\n"]
-        schematics: Vec<Schematic>,
+        schematics: Vec<Grid<char>>,
     }
 }
 
 pub struct Solution;
 
-impl Schematic {
-    fn fits(&self, other: &Self) -> bool {
-        assert_eq!(self.rows.len(), other.rows.len());
-        assert_eq!(self.rows[0].0.len(), other.rows[0].0.len());
+fn fits(lh: &Grid<char>, rh: &Grid<char>) -> bool {
+    assert_eq!(lh.height(), rh.height());
+    assert_eq!(lh.width(), rh.width());
 
-        self.rows.iter().zip(&other.rows).all(|(lh, rh)| {
-            lh.0.iter()
-                .zip(&rh.0)
-                .all(|(&lc, &rc)| lc != '#' || rc != '#')
-        })
-    }
+    lh.iter()
+        .zip(rh.iter())
+        .all(|((_, &lc), (_, &rc))| lc != '#' || rc != '#')
 }
 
 fn pairs<T>(l: &[T]) -> Vec<(&T, &T)> {
@@ -44,12 +32,17 @@ fn pairs<T>(l: &[T]) -> Vec<(&T, &T)> {
 }
 
 impl DaySolution for Solution {
-    type InputFormat = InputFormat;
-    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + 'static> {
-        let r = pairs(&input.schematics)
+    type InputFormat<'a> = InputFormat;
+    fn solve_1(input: &InputFormat) -> MyResult<impl Debug + use<>> {
+        let pairs = pairs(&input.schematics);
+        let progress = Progress::new(pairs.len());
+        let r = pairs
             .into_par_iter()
-            .filter(|(a, b)| a.fits(b))
-            .count();
+            .map(|(a, b)| -> Result<usize, Cancelled> {
+                progress.inc()?;
+                Ok(fits(a, b) as usize)
+            })
+            .try_reduce(|| 0, |lh, rh| Ok(lh + rh))?;
 
         Ok(r)
     }
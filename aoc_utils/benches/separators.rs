@@ -0,0 +1,128 @@
+use std::hint::black_box;
+
+use aoc_utils::{
+    formatted_struct, make_pattern, MyResult, Parsable, ParseBuffer, Pattern, Regex,
+    SeparatorParsable, TupleParsable,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// Day 18's input has 3450 lines of `x,y`.
+fn points_text() -> String {
+    (0..3450)
+        .map(|i| format!("{},{}", i * 7 % 71, i * 13 % 71))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Day 2's input has 1000 lines of up to 8 numbers.
+fn reports_text() -> String {
+    (0..1000)
+        .map(|i| {
+            (0..8)
+                .map(|j| ((i * 31 + j * 17) % 99).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Day 14's input has 500 lines of `p=x,y v=x,y`.
+fn robots_text() -> String {
+    (0..500)
+        .map(|i| format!("p={},{} v={},{}", i % 101, i % 103, i % 7 - 3, i % 5 - 2))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// What every separator used to be.
+fn regex(pattern: &str) -> Pattern {
+    Pattern::from(Regex::new(pattern).unwrap())
+}
+
+formatted_struct! {
+    pub struct Robot {
+        "p=",
+        p_x: i64,
+        ",",
+        p_y: i64,
+        " v=",
+        v_x: i64,
+        ",",
+        v_y: i64,
+    }
+}
+
+formatted_struct! {
+    pub struct Robots {
+        #[separated_by = "\n"]
+        robots: Vec<Robot>,
+    }
+}
+
+fn parse_points(text: &str, line: &Pattern, comma: &Pattern) -> Vec<(i32, i32)> {
+    Vec::parse_separated_with(text, line, |item| <(i32, i32)>::parse_tuple(item, comma)).unwrap()
+}
+
+fn parse_reports(text: &str, line: &Pattern, space: &Pattern) -> Vec<Vec<i32>> {
+    Vec::parse_separated_with(text, line, |row| Vec::parse_separated_by(row, space)).unwrap()
+}
+
+// What `Robots` did when every literal was a regex.
+fn parse_robots(text: &str, literals: &[Pattern; 5]) -> MyResult<Vec<[i64; 4]>> {
+    let [p, comma, v, _, line] = literals;
+    Ok(Vec::parse_separated_with(text, line, |line| {
+        let mut buffer = ParseBuffer::new(line);
+        buffer.skip(p)?;
+        Ok([
+            i64::parse(buffer.read_until(comma)?)?,
+            i64::parse(buffer.read_until(v)?)?,
+            i64::parse(buffer.read_until(comma)?)?,
+            i64::parse(buffer.read_to_end())?,
+        ])
+    })?)
+}
+
+fn separators(c: &mut Criterion) {
+    let points = points_text();
+    let mut group = c.benchmark_group("points");
+    let (line, comma) = (regex("\n"), regex(","));
+    for (name, line, comma) in [
+        ("literal", make_pattern!("\n"), make_pattern!(",")),
+        ("regex", &line, &comma),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| parse_points(black_box(&points), line, comma))
+        });
+    }
+    group.finish();
+
+    let reports = reports_text();
+    let mut group = c.benchmark_group("reports");
+    let (line, space) = (regex("\n"), regex(" "));
+    for (name, line, space) in [
+        ("literal", make_pattern!("\n"), make_pattern!(" ")),
+        ("regex", &line, &space),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| parse_reports(black_box(&reports), line, space))
+        });
+    }
+    group.finish();
+}
+
+fn literals(c: &mut Criterion) {
+    let robots = robots_text();
+    let mut group = c.benchmark_group("robots");
+    group.bench_function("literal", |b| {
+        b.iter(|| Robots::parse(black_box(&robots)).unwrap())
+    });
+    let literals = ["p=", ",", " v=", ",", "\n"].map(regex);
+    group.bench_function("regex", |b| {
+        b.iter(|| parse_robots(black_box(&robots), &literals).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, separators, literals);
+criterion_main!(benches);
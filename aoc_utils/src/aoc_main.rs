@@ -1,7 +1,17 @@
-use std::io::{stdout, Write};
+use std::{
+    fs,
+    io::{stdout, Write},
+    path::Path,
+    time::Duration,
+};
 
-use crate::{make_real_path, make_sample_path, MyResult};
-use clap::{builder::PossibleValue, Args, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use crate::{
+    cancellation::install_interrupt_handler, fuzz::fuzz, make_generated_path, make_real_path,
+    make_sample_path, MyResult,
+};
+use clap::{
+    builder::PossibleValue, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum,
+};
 
 use crate::ExistentialDaySolution;
 
@@ -22,12 +32,47 @@ struct InputSource {
     real: bool,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    Generate {
+        #[arg(long)]
+        seed: u64,
+        #[arg(long)]
+        size: usize,
+    },
+    // Mutates the selected input, or generates inputs if none is selected.
+    Fuzz {
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+        #[arg(long, default_value_t = 1000)]
+        iterations: usize,
+    },
+}
+
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
     #[arg(short, long)]
     day: String,
     #[command(flatten)]
     input_source: InputSource,
+    #[arg(short, long)]
+    timeout: Option<f64>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+fn generate(day_solution: &ExistentialDaySolution, seed: u64, size: usize) -> MyResult<()> {
+    let input = (day_solution.generate)(seed, size)?;
+    let file_path = make_generated_path(day_solution.canonical_name.parse().unwrap(), seed, size);
+    if let Some(directory) = Path::new(&file_path).parent() {
+        fs::create_dir_all(directory)?;
+    }
+    println!("Writing to {}", file_path);
+    fs::write(file_path, input)?;
+    Ok(())
 }
 
 pub fn aoc_main(day_solutions: Vec<ExistentialDaySolution>) -> MyResult<()> {
@@ -50,8 +95,13 @@ pub fn aoc_main(day_solutions: Vec<ExistentialDaySolution>) -> MyResult<()> {
         .into_iter()
         .find(|d| d.canonical_name == cli.day || d.alternative_names.contains(&&cli.day[..]))
         .unwrap();
-    let input_file = cli.input_source.input_file.unwrap_or_else(|| {
-        if cli.input_source.real {
+    if let Some(Command::Generate { seed, size }) = cli.command {
+        return generate(&day_solution, seed, size);
+    }
+    let input_source = cli.input_source;
+    let has_input = input_source.input_file.is_some() || input_source.sample || input_source.real;
+    let input_file = input_source.input_file.unwrap_or_else(|| {
+        if input_source.real {
             make_real_path(day_solution.canonical_name.parse().unwrap())
         } else {
             make_sample_path(
@@ -60,8 +110,21 @@ pub fn aoc_main(day_solutions: Vec<ExistentialDaySolution>) -> MyResult<()> {
             )
         }
     });
+    if let Some(Command::Fuzz {
+        seed,
+        size,
+        iterations,
+    }) = cli.command
+    {
+        let base_input = has_input
+            .then(|| fs::read_to_string(&input_file))
+            .transpose()?;
+        return fuzz(&day_solution, base_input.as_deref(), seed, size, iterations);
+    }
     // println!("input_file = {}", input_file);
-    let solution_result = (day_solution.solve)(&input_file)?;
+    install_interrupt_handler()?;
+    let timeout = cli.timeout.map(Duration::from_secs_f64);
+    let solution_result = (day_solution.solve)(&input_file, timeout)?;
     stdout().write_all(&solution_result)?;
     Ok(())
 }
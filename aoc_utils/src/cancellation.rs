@@ -0,0 +1,182 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use crate::MyResult;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        Default::default()
+    }
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+static CURRENT_TOKEN: Mutex<Option<CancellationToken>> = Mutex::new(None);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// The token of the solve that is currently running.
+pub fn cancellation_token() -> CancellationToken {
+    CURRENT_TOKEN
+        .lock()
+        .unwrap()
+        .get_or_insert_with(CancellationToken::new)
+        .clone()
+}
+
+pub fn check_cancelled() -> Result<(), Cancelled> {
+    cancellation_token().check()
+}
+
+// The first Ctrl-C cancels the running solve and skips the following ones, the second one exits.
+pub(crate) fn install_interrupt_handler() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::Relaxed) {
+            std::process::exit(130);
+        }
+        cancellation_token().cancel();
+    })
+}
+
+pub(crate) fn with_cancellation<R>(
+    timeout: Option<Duration>,
+    computation: impl FnOnce() -> MyResult<R>,
+) -> MyResult<R> {
+    let token = CancellationToken::new();
+    if INTERRUPTED.load(Ordering::Relaxed) {
+        token.cancel();
+    }
+    *CURRENT_TOKEN.lock().unwrap() = Some(token.clone());
+    // Reset even when the computation panics, so the next solve in this process gets a new token.
+    let _reset = ResetCurrentToken;
+    token.check()?;
+    cancel_after_timeout(&token, timeout, computation)
+}
+
+struct ResetCurrentToken;
+
+impl Drop for ResetCurrentToken {
+    fn drop(&mut self) {
+        *CURRENT_TOKEN.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+fn cancel_after_timeout<R>(
+    token: &CancellationToken,
+    timeout: Option<Duration>,
+    computation: impl FnOnce() -> R,
+) -> R {
+    let Some(timeout) = timeout else {
+        return computation();
+    };
+    let finished = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let watchdog = scope.spawn(|| {
+            let deadline = Instant::now() + timeout;
+            while !finished.load(Ordering::Relaxed) {
+                let now = Instant::now();
+                if now >= deadline {
+                    token.cancel();
+                    break;
+                }
+                thread::park_timeout(deadline - now);
+            }
+        });
+        // The scope waits for the watchdog even when the computation panics.
+        let _stop = StopWatchdog {
+            finished: &finished,
+            watchdog: watchdog.thread(),
+        };
+        computation()
+    })
+}
+
+struct StopWatchdog<'a> {
+    finished: &'a AtomicBool,
+    watchdog: &'a Thread,
+}
+
+impl Drop for StopWatchdog<'_> {
+    fn drop(&mut self) {
+        self.finished.store(true, Ordering::Relaxed);
+        self.watchdog.unpark();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_clones_share_state() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert_eq!(clone.check(), Ok(()));
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.check(), Err(Cancelled));
+    }
+    #[test]
+    fn timeout_cancels() {
+        let token = CancellationToken::new();
+        let result = cancel_after_timeout(&token, Some(Duration::from_millis(10)), || {
+            while token.check().is_ok() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            token.check()
+        });
+        assert_eq!(result, Err(Cancelled));
+    }
+    #[test]
+    fn panicking_computation_cleans_up() {
+        let start = Instant::now();
+        let mut running = None;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            with_cancellation(Some(Duration::from_secs(60)), || -> MyResult<()> {
+                running = Some(cancellation_token());
+                panic!("solve failed");
+            })
+        }));
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(10));
+        let running = running.unwrap();
+        assert!(!Arc::ptr_eq(
+            &running.cancelled,
+            &cancellation_token().cancelled
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt::{Display, Formatter};
+
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+fn push_shortest_match(hir: &Hir, text: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => text.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(Class::Unicode(class)) => {
+            let chars = class
+                .ranges()
+                .iter()
+                .map(|range| range.start()..=range.end());
+            if let Some(c) = pick_char(chars) {
+                text.push(c);
+            }
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let chars = class
+                .ranges()
+                .iter()
+                .map(|range| char::from(range.start())..=char::from(range.end()));
+            if let Some(c) = pick_char(chars) {
+                text.push(c);
+            }
+        }
+        HirKind::Repetition(repetition) => {
+            for _ in 0..repetition.min {
+                push_shortest_match(&repetition.sub, text);
+            }
+        }
+        HirKind::Capture(capture) => push_shortest_match(&capture.sub, text),
+        HirKind::Concat(hirs) => hirs.iter().for_each(|hir| push_shortest_match(hir, text)),
+        HirKind::Alternation(hirs) => push_shortest_match(&hirs[0], text),
+    }
+}
+
+// A space reads best for classes like `\s` or `.`, otherwise the lowest char.
+fn pick_char(
+    mut ranges: impl Iterator<Item = std::ops::RangeInclusive<char>> + Clone,
+) -> Option<char> {
+    if ranges.clone().any(|range| range.contains(&' ')) {
+        return Some(' ');
+    }
+    ranges.next().map(|range| *range.start())
+}
+
+// The text `Display` writes for a literal of `formatted_struct!`: the shortest match of the regex,
+// taking the first alternative and the first char of a class.
+pub fn canonical_text(pattern: &str) -> String {
+    let hir = regex_syntax::parse(pattern)
+        .unwrap_or_else(|e| panic!("{:?} is not a valid regex: {}", pattern, e));
+    let mut text = String::new();
+    push_shortest_match(&hir, &mut text);
+    text
+}
+
+// Writes the items with the separator between them, for `separated_by` fields. `write_item` gets
+// the formatter back, so that nested collections can be written with their own separators.
+pub fn write_separated<I: WriteSeparated>(
+    f: &mut Formatter<'_>,
+    items: I,
+    separator: &str,
+    write_item: impl FnMut(&mut Formatter<'_>, I::Item) -> std::fmt::Result,
+) -> std::fmt::Result {
+    items.write_separated(f, separator, write_item)
+}
+
+// What a `separated_by` field writes: the items of a collection, or the text of a `Separated` as
+// it was read, since its items might not parse.
+pub trait WriteSeparated {
+    type Item;
+    fn write_separated(
+        self,
+        f: &mut Formatter<'_>,
+        separator: &str,
+        write_item: impl FnMut(&mut Formatter<'_>, Self::Item) -> std::fmt::Result,
+    ) -> std::fmt::Result;
+}
+
+impl<I: IntoIterator> WriteSeparated for I {
+    type Item = I::Item;
+    fn write_separated(
+        self,
+        f: &mut Formatter<'_>,
+        separator: &str,
+        mut write_item: impl FnMut(&mut Formatter<'_>, I::Item) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        for (i, item) in self.into_iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", separator)?;
+            }
+            write_item(f, item)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes the items of a tuple with the separator between them, for `tuple_sep` fields and maps.
+pub trait WriteTuple {
+    fn write_tuple(&self, f: &mut Formatter<'_>, separator: &str) -> std::fmt::Result;
+}
+
+impl<T: WriteTuple> WriteTuple for &T {
+    fn write_tuple(&self, f: &mut Formatter<'_>, separator: &str) -> std::fmt::Result {
+        (*self).write_tuple(f, separator)
+    }
+}
+
+macro_rules! write_tuple {
+    ($first_index:tt $first_item:ident $(, $index:tt $item:ident)+) => {
+        impl<$first_item: Display $(, $item: Display)+> WriteTuple for ($first_item, $($item),+) {
+            fn write_tuple(&self, f: &mut Formatter<'_>, separator: &str) -> std::fmt::Result {
+                write!(f, "{}", self.$first_index)?;
+                $(write!(f, "{}{}", separator, self.$index)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+write_tuple!(0 A, 1 B);
+write_tuple!(0 A, 1 B, 2 C);
+write_tuple!(0 A, 1 B, 2 C, 3 D);
+
+// How `formatted_struct!` writes a field: `(&FieldWriter(value)).write_field(f)` with both traits in
+// scope picks `WriteOptionalField` for an `Option`, which writes `None` as nothing, and `Display`
+// otherwise.
+pub struct FieldWriter<'a, T>(pub &'a T);
+
+pub trait WriteField {
+    fn write_field(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
+}
+
+impl<T: Display> WriteField for &FieldWriter<'_, T> {
+    fn write_field(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub trait WriteOptionalField {
+    fn write_field(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
+}
+
+impl<T: Display> WriteOptionalField for FieldWriter<'_, Option<T>> {
+    fn write_field(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(value) => write!(f, "{}", value),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_matches() {
+        assert_eq!(canonical_text(" +"), " ");
+        assert_eq!(canonical_text("X\\+"), "X+");
+        assert_eq!(canonical_text("\\|"), "|");
+        assert_eq!(canonical_text("\n\n"), "\n\n");
+        assert_eq!(canonical_text("a|bc"), "a");
+        assert_eq!(canonical_text("[,;]\\s*x?"), ",");
+        assert_eq!(canonical_text("\\s+"), " ");
+    }
+}
@@ -0,0 +1,190 @@
+use std::cmp::Reverse;
+
+use regex::{Captures, Regex};
+
+use crate::{Parsable, ParseError, ParseErrorKind};
+
+// A type read from the groups of a regex match, so that it can be found anywhere in a text.
+// `#[derive(Parsable)]` implements it for `#[parse(captures = "...")]`, with one regex per
+// variant of an enum, named after it.
+pub trait CapturesParsable<'a>: Sized {
+    fn regexes() -> Vec<(&'static str, &'static Regex)>;
+    // Whether the longest of the matches at the same place wins, instead of the first regex.
+    fn longest_match() -> bool {
+        false
+    }
+    // `index` is the index of the regex that matched.
+    fn from_captures(index: usize, captures: &Captures<'a>) -> Result<Self, ParseError>;
+}
+
+// Every non-overlapping match of `A` in the text, leftmost first, skipping the text in between.
+// When several regexes match at the same place, the first one wins, like in an alternation, or the
+// longest one with `#[parse(longest_match)]`. With the `strict_parsing` feature, a tie is an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matches<A>(pub Vec<A>);
+
+impl<'a, A: CapturesParsable<'a>> Parsable<'a> for Matches<A> {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        let regexes = A::regexes();
+        let longest_match = A::longest_match();
+        let strict = cfg!(feature = "strict_parsing");
+        let mut next_matches = regexes
+            .iter()
+            .map(|(_, regex)| regex.captures(text))
+            .collect::<Vec<_>>();
+        let mut items = Vec::new();
+        let mut start = 0;
+        while start <= text.len() {
+            // Only the matches that overlap the previous one have to be searched for again.
+            for ((_, regex), next_match) in regexes.iter().zip(&mut next_matches) {
+                if next_match
+                    .as_ref()
+                    .is_some_and(|captures| captures.get(0).unwrap().start() < start)
+                {
+                    *next_match = regex.captures_at(text, start);
+                }
+            }
+            // The leftmost matches, longest first if they are preferred.
+            let mut candidates = next_matches
+                .iter()
+                .enumerate()
+                .filter_map(|(index, captures)| Some((index, captures.as_ref()?.get(0).unwrap())))
+                .map(|(index, whole)| {
+                    let length = if longest_match { whole.len() } else { 0 };
+                    ((whole.start(), Reverse(length)), index)
+                })
+                .collect::<Vec<_>>();
+            candidates.sort();
+            let Some(&(best, index)) = candidates.first() else {
+                break;
+            };
+            let captures = next_matches[index].as_ref().unwrap();
+            let whole = captures.get(0).unwrap();
+            let tied = candidates
+                .iter()
+                .take_while(|&&(key, _)| key == best)
+                .map(|&(_, index)| regexes[index].0)
+                .collect::<Vec<_>>();
+            if strict && tied.len() > 1 {
+                let kind = ParseErrorKind::AmbiguousVariants { variants: tied };
+                return Err(ParseError::new(kind, whole.as_str()).with_index(items.len()));
+            }
+            let item = A::from_captures(index, captures)
+                .map_err(|e| e.at(whole.as_str()).with_index(items.len()))?;
+            items.push(item);
+            start = match text[whole.end()..].chars().next() {
+                Some(c) if whole.is_empty() => whole.end() + c.len_utf8(),
+                None if whole.is_empty() => break,
+                _ => whole.end(),
+            };
+        }
+        Ok(Matches(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MyResult;
+
+    #[derive(crate::Parsable, PartialEq, Eq, Debug)]
+    #[parse(captures = r"mul\((?<lh>\d+),(?<rh>\d+)\)")]
+    struct DerivedMul {
+        lh: i32,
+        rh: i32,
+    }
+
+    #[derive(crate::Parsable, PartialEq, Eq, Debug)]
+    enum DerivedToken<'a> {
+        #[parse(captures = r"do\(\)")]
+        Do,
+        #[parse(captures = r"do(n't)?\((\w*)\)")]
+        Call(Option<&'a str>, &'a str),
+        #[parse(captures = r"(?<digits>\d+)(?<unit>[a-z]+)?")]
+        Number { digits: u32, unit: Option<&'a str> },
+    }
+
+    #[derive(crate::Parsable, PartialEq, Eq, Debug)]
+    #[parse(longest_match)]
+    enum DerivedLongest {
+        #[parse(captures = "ab")]
+        Short,
+        #[parse(captures = "abc")]
+        Long,
+    }
+
+    #[test]
+    fn derive_captures() -> MyResult<()> {
+        assert_eq!(DerivedMul::parse("mul(2,4)")?, DerivedMul { lh: 2, rh: 4 });
+        assert!(DerivedMul::parse("mul(2,4)x").is_err());
+        assert_eq!(
+            DerivedToken::parse("don't(x)")?,
+            DerivedToken::Call(Some("n't"), "x")
+        );
+        assert_eq!(
+            DerivedToken::parse("12")?,
+            DerivedToken::Number {
+                digits: 12,
+                unit: None
+            }
+        );
+        let error = DerivedMul::parse("mul(2,99999999999)").unwrap_err();
+        assert_eq!(error.path(), "DerivedMul.rh");
+        Ok(())
+    }
+    #[test]
+    fn find_matches() -> MyResult<()> {
+        let text = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        assert_eq!(
+            Matches::<DerivedMul>::parse(text)?.0,
+            vec![
+                DerivedMul { lh: 2, rh: 4 },
+                DerivedMul { lh: 5, rh: 5 },
+                DerivedMul { lh: 11, rh: 8 },
+                DerivedMul { lh: 8, rh: 5 },
+            ]
+        );
+        assert_eq!(Matches::<DerivedMul>::parse("")?.0, vec![]);
+        let error = Matches::<DerivedMul>::parse("mul(1,2) mul(3,99999999999)").unwrap_err();
+        assert_eq!(error.path(), "DerivedMul[1].rh");
+        // `do()` is also a `Call`, so it's a `Do` as its regex is first, or an error when strict.
+        // `12kg` is not also read as `12`.
+        let tokens = Matches::<DerivedToken>::parse("do() 12kg,don't()3");
+        if cfg!(feature = "strict_parsing") {
+            let error = tokens.unwrap_err();
+            assert_eq!(error.path(), "[0]");
+            assert_eq!(
+                error.kind(),
+                &ParseErrorKind::AmbiguousVariants {
+                    variants: vec!["Do", "Call"]
+                }
+            );
+        } else {
+            assert_eq!(
+                tokens?.0,
+                vec![
+                    DerivedToken::Do,
+                    DerivedToken::Number {
+                        digits: 12,
+                        unit: Some("kg")
+                    },
+                    DerivedToken::Call(Some("n't"), ""),
+                    DerivedToken::Number {
+                        digits: 3,
+                        unit: None
+                    },
+                ]
+            );
+        }
+        Ok(())
+    }
+    #[test]
+    fn find_longest_matches() -> MyResult<()> {
+        assert_eq!(
+            Matches::<DerivedLongest>::parse("abcab")?.0,
+            vec![DerivedLongest::Long, DerivedLongest::Short]
+        );
+        assert_eq!(DerivedLongest::parse("ab")?, DerivedLongest::Short);
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+#[macro_export]
+macro_rules! char_enum {
+    (
+        $(#[$($enum_meta:meta),*])?
+        $vis:vis enum $enum_name:ident
+        {
+            $($variant_name:ident = $char:literal),+
+            $(,)?
+        }
+    ) => {
+        $crate::make_item!{enum $enum_name vis=($vis) $(meta=$($enum_meta),*)? { $($variant_name),+ }}
+
+        impl $enum_name {
+            pub const CHARS: &'static [char] = &[$($char),+];
+
+            // By reference, as the enum may not be `Copy`.
+            #[allow(clippy::wrong_self_convention)]
+            pub fn to_char(&self) -> char {
+                match self {
+                    $($enum_name::$variant_name => $char),+
+                }
+            }
+        }
+
+        impl TryFrom<char> for $enum_name {
+            type Error = $crate::ParseError;
+
+            fn try_from(c: char) -> Result<$enum_name, $crate::ParseError> {
+                match c {
+                    $($char => Ok($enum_name::$variant_name),)+
+                    _ => Err($crate::ParseErrorKind::InvalidChar {
+                        type_name: stringify!($enum_name),
+                        found: c.to_string(),
+                        valid_chars: Self::CHARS,
+                    }
+                    .into()),
+                }
+            }
+        }
+
+        impl $crate::Parsable<'_> for $enum_name {
+            fn parse(text: &str) -> Result<$enum_name, $crate::ParseError> {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => $enum_name::try_from(c).map_err(|e| e.at(text)),
+                    _ => Err($crate::ParseError::new(
+                        $crate::ParseErrorKind::InvalidChar {
+                            type_name: stringify!($enum_name),
+                            found: text.to_string(),
+                            valid_chars: Self::CHARS,
+                        },
+                        text,
+                    )),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.to_char())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    char_enum! {
+        #[derive(PartialEq, Eq, Debug, Clone, Copy)]
+        enum Tile {
+            Wall = '#',
+            Empty = '.',
+        }
+    }
+
+    use crate::{Grid, MyResult, Parsable};
+
+    #[test]
+    fn parse_and_render() -> MyResult<()> {
+        assert_eq!(Tile::parse("#")?, Tile::Wall);
+        assert_eq!(Tile::try_from('.')?, Tile::Empty);
+        assert_eq!(Tile::Wall.to_char(), '#');
+        let grid = Grid::<Tile>::parse("#.\n.#")?;
+        assert_eq!(grid.to_string(), "#.\n.#");
+        Ok(())
+    }
+    #[test]
+    fn parse_err() {
+        for text in ["x", "##"] {
+            assert_eq!(
+                Tile::parse(text).unwrap_err().to_string(),
+                format!("{:?} is not a valid Tile, expected one of '#', '.'", text)
+            );
+        }
+    }
+}
@@ -6,13 +6,22 @@ use std::{
     time::{Duration, Instant},
 };
 
+use memmap2::Mmap;
 use termcolor::{Buffer, ColorSpec, WriteColor};
 
-use crate::{MyResult, Parsable};
+use crate::{
+    cancellation::with_cancellation,
+    fuzz::{check_variants, CheckOutcome, Variant},
+    parse_input,
+    progress::with_progress_rendering,
+    MyResult, Normalization, Parsable, ParseError,
+};
 
 pub struct ExistentialDaySolution {
     #[allow(clippy::type_complexity)]
-    pub solve: Box<dyn Fn(&str) -> MyResult<Vec<u8>>>,
+    pub solve: Box<dyn Fn(&str, Option<Duration>) -> MyResult<Vec<u8>>>,
+    pub generate: fn(u64, usize) -> MyResult<String>,
+    pub check_variants: fn(&str) -> CheckOutcome,
     pub canonical_name: &'static str,
     pub leading_zeros_name: &'static str,
     pub alternative_names: Vec<&'static str>,
@@ -48,18 +57,94 @@ fn pretty_print<A: Debug + 'static>(result: MyResult<A>) -> Vec<u8> {
     display_buffer.into_inner()
 }
 
-pub trait DaySolution {
-    type InputFormat;
+// `register_days!` requires it of each day's `Solution`, so a day that doesn't export one fails to
+// compile with this message, or with a missing `Solution` in its module.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is registered as a day, but doesn't implement `DaySolution`",
+    note = "each `src/solutions/day_NN.rs` must export a `Solution` implementing `DaySolution`"
+)]
+pub trait DaySolution: Sized + 'static {
+    // Can borrow from the input text, which lives for the whole run of the day. The answers can't
+    // borrow from it, so implementations return `MyResult<impl Debug + use<>>`.
+    type InputFormat<'a>;
 
-    fn solve_1(_input: &Self::InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_1(_input: &Self::InputFormat<'_>) -> MyResult<impl Debug + use<Self>> {
         Err::<(), Box<dyn Error>>(From::from("solve_1 not implemented"))
     }
-    fn solve_2(_input: &Self::InputFormat) -> MyResult<impl Debug + 'static> {
+    fn solve_2(_input: &Self::InputFormat<'_>) -> MyResult<impl Debug + use<Self>> {
         Err::<(), Box<dyn Error>>(From::from("solve_2 not implemented"))
     }
+    // Other implementations of the parts, which the `fuzz` subcommand checks against `solve_1` and `solve_2`.
+    fn variants_1() -> Vec<Variant<Self>> {
+        Vec::new()
+    }
+    fn variants_2() -> Vec<Variant<Self>> {
+        Vec::new()
+    }
     fn preferred_sample_input() -> i32 {
         0
     }
+    // Applied to the input before parsing it. Days can trim the line ends too, or turn parts of it
+    // off where whitespace matters.
+    fn normalization() -> Normalization {
+        Normalization::default()
+    }
+    // Maps the input file into memory instead of reading it, for huge inputs parsed lazily with
+    // `Separated`. Normalizing `\r\n` line ends or trailing spaces copies the text anyway.
+    fn memory_map_input() -> bool {
+        false
+    }
+    // Makes a random input of roughly `size` (the meaning is up to the day), the same for the same `seed`.
+    fn generate(_seed: u64, _size: usize) -> MyResult<String> {
+        Err(From::from("generate not implemented"))
+    }
+}
+
+fn run_part<A: Debug + 'static>(
+    label: &str,
+    timeout: Option<Duration>,
+    computation: impl FnOnce() -> MyResult<A>,
+) -> (MyResult<A>, Duration) {
+    with_progress_rendering(label, || {
+        measure_time(|| with_cancellation(timeout, computation))
+    })
+}
+
+// The items of `Separated` are parsed while solving, so their errors come out of the parts.
+fn locate_error(error: Box<dyn Error>, input: &str) -> Box<dyn Error> {
+    match error.downcast::<ParseError>() {
+        Ok(error) => Box::new(error.locate(input)),
+        Err(error) => error,
+    }
+}
+
+enum InputText {
+    Read(String),
+    Mapped(Mmap),
+}
+
+impl InputText {
+    // Files that can't be mapped, like pipes, are read instead.
+    fn open(filename: &str, memory_map: bool) -> MyResult<InputText> {
+        let mut input_file = File::open(filename)?;
+        if memory_map {
+            // SAFETY: the mapping changes if the file does, and reading it after the file is
+            // truncated is undefined behaviour. Days only opt in for input files that nothing
+            // writes to while they run.
+            if let Ok(mapped_file) = unsafe { Mmap::map(&input_file) } {
+                return Ok(InputText::Mapped(mapped_file));
+            }
+        }
+        let mut file_content = String::new();
+        input_file.read_to_string(&mut file_content)?;
+        Ok(InputText::Read(file_content))
+    }
+    fn as_str(&self) -> MyResult<&str> {
+        match self {
+            InputText::Read(text) => Ok(text),
+            InputText::Mapped(mapped_file) => Ok(std::str::from_utf8(mapped_file)?),
+        }
+    }
 }
 
 fn measure_time<R, F: FnOnce() -> R>(computation: F) -> (R, Duration) {
@@ -72,7 +157,7 @@ fn measure_time<R, F: FnOnce() -> R>(computation: F) -> (R, Duration) {
 
 pub fn make_day_solution<A: DaySolution>(solution_filename: &'static str) -> ExistentialDaySolution
 where
-    A::InputFormat: Parsable,
+    for<'a> A::InputFormat<'a>: Parsable<'a>,
 {
     let no_rs_sufix = solution_filename.trim_end_matches(".rs");
     let no_day_prefix = no_rs_sufix.trim_start_matches("day_");
@@ -87,13 +172,14 @@ where
         alternative_names.push(no_day_prefix);
     }
     ExistentialDaySolution {
-        solve: Box::new(move |input_filename| {
-            let mut input_file = File::open(input_filename)?;
-            let mut file_content = String::new();
-            input_file.read_to_string(&mut file_content)?;
-            let file_content = file_content.trim_end_matches("\n");
-            let input = A::InputFormat::parse(file_content)?;
-            let (result_1, part_1_time) = measure_time(|| A::solve_1(&input));
+        solve: Box::new(move |input_filename, timeout| {
+            let input_text = InputText::open(input_filename, A::memory_map_input())?;
+            let file_content = A::normalization().normalize(input_text.as_str()?);
+            let file_content: &str = &file_content;
+            let input = parse_input::<A::InputFormat<'_>>(file_content)?;
+            let (result_1, part_1_time) = run_part("Part 1", timeout, || {
+                A::solve_1(&input).map_err(|e| locate_error(e, file_content))
+            });
             let mut display_buffer = Buffer::ansi();
             display_buffer
                 .set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Yellow)))
@@ -105,7 +191,9 @@ where
             display_buffer
                 .set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Yellow)))
                 .unwrap();
-            let (result_2, part_2_time) = measure_time(|| A::solve_2(&input));
+            let (result_2, part_2_time) = run_part("Part 2", timeout, || {
+                A::solve_2(&input).map_err(|e| locate_error(e, file_content))
+            });
             display_buffer.write_all(
                 &format!("\nPart 2: ({:.3}s)\n", part_2_time.as_secs_f64()).into_bytes(),
             )?;
@@ -114,6 +202,8 @@ where
             display_buffer.reset()?;
             Ok(display_buffer.into_inner())
         }),
+        generate: A::generate,
+        check_variants: check_variants::<A>,
         canonical_name: no_leading_digits,
         leading_zeros_name: no_day_prefix,
         alternative_names,
@@ -1,37 +1,98 @@
-use crate::MyResult;
-
 #[macro_export]
 macro_rules! make_regex {
-    ($expr:expr) => {
-        {
-            use std::sync::LazyLock;
-            use $crate::Regex;
-            static COMPILED_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new($expr).unwrap());
-            LazyLock::force(&COMPILED_REGEX)
+    ($expr:expr) => {{
+        use std::sync::LazyLock;
+        use $crate::Regex;
+        static COMPILED_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new($expr).unwrap());
+        LazyLock::force(&COMPILED_REGEX)
+    }};
+}
+
+// Plain literals are known at compile time, so they don't need a regex or a lock.
+#[macro_export]
+macro_rules! make_pattern {
+    ($expr:expr) => {{
+        use std::sync::LazyLock;
+        use $crate::Pattern;
+        const TEXT: &str = $expr;
+        static LITERAL: Option<Pattern> = Pattern::plain_literal(TEXT);
+        static COMPILED_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern::new(TEXT).unwrap());
+        match &LITERAL {
+            Some(literal) => literal,
+            None => LazyLock::force(&COMPILED_PATTERN),
         }
-    };
+    }};
+}
+
+#[macro_export]
+macro_rules! make_canonical_text {
+    ($lit:literal) => {{
+        use std::sync::LazyLock;
+        static CANONICAL_TEXT: LazyLock<String> = LazyLock::new(|| $crate::canonical_text($lit));
+        CANONICAL_TEXT.as_str()
+    }};
 }
 
 #[macro_export]
 macro_rules! parse_single {
     (type=$type:ty, str=$expr:expr) => {
-        <$type as $crate::Parsable>::parse($expr)?
+        <$type as $crate::Parsable<'_>>::parse($expr)
     };
-    (type=$type:ty, separator=$separator:literal, str=$expr:expr) => {
-        <$type as $crate::SeparatorParsable>::parse_separated_by(
+    (type=$type:ty, tuple=$tuple:literal, str=$expr:expr) => {
+        <$type as $crate::TupleParsable<'_>>::parse_tuple($expr, $crate::make_pattern!($tuple))
+    };
+    (
+        type=$type:ty, separator=[= $separator:literal],
+        $(tuple=$tuple:literal,)? str=$expr:expr
+    ) => {
+        $crate::parse_single!(
+            type=$type, separators=[$separator], $(tuple=$tuple,)? str=$expr
+        )
+    };
+    (
+        type=$type:ty, separator=[($($separator:literal),+ $(,)?)],
+        $(tuple=$tuple:literal,)? str=$expr:expr
+    ) => {
+        $crate::parse_single!(
+            type=$type, separators=[$($separator),+], $(tuple=$tuple,)? str=$expr
+        )
+    };
+    (type=$type:ty, separators=[], $(tuple=$tuple:literal,)? str=$expr:expr) => {
+        $crate::parse_single!(type=$type, $(tuple=$tuple,)? str=$expr)
+    };
+    // Each separator after the first splits the items of the previous one.
+    (
+        type=$type:ty, separators=[$first:literal $(, $rest:literal)*],
+        $(tuple=$tuple:literal,)? str=$expr:expr
+    ) => {
+        <$type as $crate::SeparatorParsable<'_>>::parse_separated_with(
             $expr,
-            $crate::make_regex!($separator),
-        )?
+            $crate::make_pattern!($first),
+            |item_text| {
+                $crate::parse_single!(
+                    type=<$type as $crate::SeparatorParsable<'_>>::Item,
+                    separators=[$($rest),*],
+                    $(tuple=$tuple,)?
+                    str=item_text
+                )
+            },
+        )
     };
 }
 
 #[macro_export]
 macro_rules! single_read {
     ($buffer:ident) => {
-        $buffer.read_to_end()
+        Ok::<_, $crate::ParseError>($buffer.read_to_end())
     };
     ($buffer:ident, $lit:literal) => {
-        $buffer.read_until($crate::make_regex!($lit))?
+        $buffer.read_until($crate::make_pattern!($lit))
+    };
+    ($buffer:ident, width=$width:literal $(, $lit:literal)?) => {
+        $buffer.read_width($width).and_then(|field_text| {
+            $($buffer.skip($crate::make_pattern!($lit))?;)?
+            Ok(field_text)
+        })
     };
 }
 
@@ -40,23 +101,135 @@ macro_rules! make_reader_body {
     (
         constructor=$constructor_name:ident
         text=($text:expr)
+        $(returning=$returning:ident)?
         $(leading_literal=$leading_literal:literal)?
-        $(name=$name:ident, type=$type:ty {$(until=$lit:literal)? $(separator=$separator:literal)?}),*
+        members=[$($member:ident),*]
+        $(
+            name=$name:tt, type=$type:ty
+            {
+                $(width=$width:literal)? $(until=$lit:literal)?
+                $(separator=$separator:tt)? $(tuple=$tuple:literal)?
+                $(validate=($($validation:tt)+))?
+            }
+        ),* $(,)?
     ) => {
         let mut buffer = $crate::ParseBuffer::new($text);
-        $(buffer.skip($crate::make_regex!($leading_literal))?)?;
-        $(let $name = $crate::parse_single!(type=$type, $(separator=$separator,)? str=$crate::single_read!(buffer$(, $lit)?));)*
-        Ok($constructor_name {
-            $($name),*
+        $(buffer.skip($crate::make_pattern!($leading_literal))?;)?
+        $(
+            let $name = $crate::single_read!(buffer $(, width=$width)? $(, $lit)?)
+                .and_then(|field_text| {
+                    $crate::parse_single!(
+                        type=$type,
+                        $(separator=$separator,)?
+                        $(tuple=$tuple,)?
+                        str=field_text
+                    )
+                        $(.and_then(|value| {
+                            let field = &value;
+                            $crate::validate_field!(field, $($validation)+)?;
+                            Ok(value)
+                        }))?
+                        .map_err(|e| e.at(field_text))
+                })
+                .map_err(|e| e.with_field(stringify!($name)))?;
+        )*
+        let parsed = $constructor_name {
+            $($member),*
+        };
+        $crate::reader_result!(parsed, buffer $(, $returning)?)
+    }
+}
+
+#[macro_export]
+macro_rules! reader_result {
+    ($parsed:ident, $buffer:ident) => {
+        Ok($parsed)
+    };
+    // Enum variants also return how much of the text they left unread, for `#[longest_match]`.
+    ($parsed:ident, $buffer:ident, with_unread) => {
+        Ok(($parsed, $buffer.remaining().len()))
+    };
+}
+
+#[macro_export]
+macro_rules! write_single {
+    ($f:ident, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{WriteField as _, WriteOptionalField as _};
+        (&$crate::FieldWriter($value)).write_field($f)
+    }};
+    ($f:ident, $value:expr, tuple=$tuple:literal) => {
+        $crate::WriteTuple::write_tuple(&$value, $f, $crate::make_canonical_text!($tuple))
+    };
+    ($f:ident, $value:expr, separator=[= $separator:literal] $(, tuple=$tuple:literal)?) => {
+        $crate::write_single!($f, $value, separators=[$separator] $(, tuple=$tuple)?)
+    };
+    (
+        $f:ident, $value:expr, separator=[($($separator:literal),+ $(,)?)]
+        $(, tuple=$tuple:literal)?
+    ) => {
+        $crate::write_single!($f, $value, separators=[$($separator),+] $(, tuple=$tuple)?)
+    };
+    ($f:ident, $value:expr, separators=[] $(, tuple=$tuple:literal)?) => {
+        $crate::write_single!($f, $value $(, tuple=$tuple)?)
+    };
+    (
+        $f:ident, $value:expr, separators=[$first:literal $(, $rest:literal)*]
+        $(, tuple=$tuple:literal)?
+    ) => {
+        $crate::write_separated($f, $value, $crate::make_canonical_text!($first), |f, item| {
+            $crate::write_single!(f, item, separators=[$($rest),*] $(, tuple=$tuple)?)
         })
+    };
+}
+
+// What `Display` writes for an ignored `_: T` field. It's only expanded in `#[display]` types, so
+// the others can leave the text out.
+#[macro_export]
+macro_rules! ignored_field_text {
+    ($type:ty, $display:literal) => {
+        &$display
+    };
+    ($type:ty) => {
+        compile_error!(concat!(
+            "`_: ",
+            stringify!($type),
+            "` needs the text `Display` writes for it, ",
+            "like `_: ",
+            stringify!($type),
+            " = \"...\"`"
+        ))
+    };
+}
+
+// The inverse of `make_reader_body!`, writing each literal as its `canonical_text`.
+#[macro_export]
+macro_rules! make_writer_body {
+    (
+        formatter=$f:ident
+        $(leading_literal=$leading_literal:literal)?
+        $(
+            value=($value:expr)
+            {$(until=$lit:literal)? $(separator=$separator:tt)? $(tuple=$tuple:literal)?}
+        ),* $(,)?
+    ) => {
+        $(write!($f, "{}", $crate::make_canonical_text!($leading_literal))?;)?
+        $(
+            $crate::write_single!($f, $value $(, separator=$separator)? $(, tuple=$tuple)?)?;
+            $(write!($f, "{}", $crate::make_canonical_text!($lit))?;)?
+        )*
+        Ok(())
     }
 }
 
 #[macro_export]
 macro_rules! make_item {
-    ($item_type:tt $item_name:ident vis=($vis:vis) $(meta=$($item_meta:meta),*)? {$($body_token:tt)*}) => {
+    (
+        $item_type:tt $item_name:ident $(<$lifetime:lifetime>)? vis=($vis:vis)
+        $(meta=$($item_meta:meta),*)? {$($body_token:tt)*}
+    ) => {
         $(#[$($item_meta),*])?
-        $vis $item_type $item_name {
+        $vis $item_type $item_name $(<$lifetime>)? {
             $($body_token)*
         }
     };
@@ -64,97 +237,399 @@ macro_rules! make_item {
 
 #[macro_export]
 macro_rules! formatted_struct {
+    // `#[display]` before the other attributes also implements `Display`, which needs every field
+    // to implement it too.
     (
+        #[display]
         $(#[$($struct_meta:meta),*])?
-        $vis: vis struct $struct_name:ident
+        $vis:vis struct $struct_name:ident $(<$lifetime:lifetime>)?
         {
-            $($leading_literal:literal,)?
-            $(
-                $(#[separated_by=$separator:literal])?
-                $name:ident : $type:ty,
-                $($lit:literal)?
-            ),*
-            $(,)?
+            $($body:tt)*
         }
     ) => {
-        $crate::make_item!{struct $struct_name vis=($vis) $(meta=$($struct_meta),*)? { $($name:$type),*}}
+        $crate::formatted_struct!{
+            @fields_start
+            then=[@struct [$(#[$($struct_meta),*])?] [$vis] $struct_name [$($lifetime)?] [display]]
+            rest=[$($body)*]
+        }
+    };
+    (
+        $(#[$($struct_meta:meta),*])?
+        $vis:vis struct $struct_name:ident $(<$lifetime:lifetime>)?
+        {
+            $($body:tt)*
+        }
+    ) => {
+        $crate::formatted_struct!{
+            @fields_start
+            then=[@struct [$(#[$($struct_meta),*])?] [$vis] $struct_name [$($lifetime)?] []]
+            rest=[$($body)*]
+        }
+    };
+    (
+        @struct [$(#[$($struct_meta:meta),*])?] [$vis:vis] $struct_name:ident [$($lifetime:lifetime)?]
+        [$($display:ident)?]
+        leading=[$($leading_literal:literal)?] members=[$($member:ident,)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+    ) => {
+        $crate::make_item!{
+            struct $struct_name $(<$lifetime>)? vis=($vis) $(meta=$($struct_meta),*)? { $($fields)* }
+        }
 
-        impl $crate::Parsable for $struct_name{
-            fn parse(text: &str) -> MyResult<$struct_name> {
-                $crate::make_reader_body!{
-                    constructor=$struct_name
-                    text=(text)
+        // Fields borrowing for `'a` can be parsed from any text that outlives it.
+        impl<'parse_text $(: $lifetime, $lifetime)?> $crate::Parsable<'parse_text> for $struct_name $(<$lifetime>)? {
+            fn parse(text: &'parse_text str) -> Result<Self, $crate::ParseError> {
+                let parse_fields = || -> Result<Self, $crate::ParseError> {
+                    $crate::make_reader_body!{
+                        constructor=$struct_name
+                        text=(text)
+                        $(leading_literal=$leading_literal)?
+                        members=[$($member),*]
+                        $($readers)*
+                    }
+                };
+                parse_fields().map_err(|e| e.with_root(stringify!($struct_name)))
+            }
+        }
+
+        $crate::formatted_struct!{
+            @display [$($display)?] $struct_name [$($lifetime)?] self f {
+                let $struct_name { $($member),* } = self;
+                $crate::make_writer_body!{
+                    formatter=f
                     $(leading_literal=$leading_literal)?
-                    $(name=$name, type=$type {$(until=$lit)? $(separator=$separator)?}),*
+                    $($writers)*
                 }
             }
         }
     };
+    (@display [] $($rest:tt)*) => {};
+    (
+        @display [display] $name:ident [$($lifetime:lifetime)?] $self:tt $f:ident { $($body:tt)* }
+    ) => {
+        impl $(<$lifetime>)? std::fmt::Display for $name $(<$lifetime>)? {
+            fn fmt(&$self, $f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                $($body)*
+            }
+        }
+    };
+    // The fields of a struct or of a struct variant are munched one at a time, as `_` isn't an
+    // `ident`. Ignored fields are read and dropped, and `Display` writes the text given for them, or
+    // nothing for a bare `_`. Once done, the members, field definitions, readers and writers are
+    // passed on to `then`.
+    (@fields_start then=$then:tt rest=[$leading_literal:literal $(, $($rest:tt)*)?]) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=[$leading_literal]
+            members=[] fields=[] readers=[] writers=[] rest=[$($($rest)*)?]
+        }
+    };
+    (@fields_start then=$then:tt rest=[$($rest:tt)*]) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=[]
+            members=[] fields=[] readers=[] writers=[] rest=[$($rest)*]
+        }
+    };
+    (
+        @fields then=$then:tt leading=$leading:tt
+        members=[$($members:tt)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+        rest=[
+            $(#[separated_by $($separator:tt)+])?
+            $(#[key_value_sep = $key_value_sep:literal])?
+            $(#[tuple_sep = $tuple_sep:literal])?
+            $(#[width = $width:literal])?
+            $(#[validate($($validation:tt)+)])?
+            $name:ident : $type:ty, $lit:literal $(, $($rest:tt)*)?
+        ]
+    ) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=$leading
+            members=[$($members)* $name,] fields=[$($fields)* $name: $type,]
+            readers=[$($readers)* name=$name, type=$type {$(width=$width)? until=$lit $(separator=[$($separator)+])? $(tuple=$key_value_sep)? $(tuple=$tuple_sep)? $(validate=($($validation)+))?},]
+            writers=[$($writers)* value=($name) {until=$lit $(separator=[$($separator)+])? $(tuple=$key_value_sep)? $(tuple=$tuple_sep)?},]
+            rest=[$($($rest)*)?]
+        }
+    };
     (
+        @fields then=$then:tt leading=$leading:tt
+        members=[$($members:tt)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+        rest=[
+            $(#[separated_by $($separator:tt)+])?
+            $(#[key_value_sep = $key_value_sep:literal])?
+            $(#[tuple_sep = $tuple_sep:literal])?
+            $(#[width = $width:literal])?
+            $(#[validate($($validation:tt)+)])?
+            $name:ident : $type:ty $(, $($rest:tt)*)?
+        ]
+    ) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=$leading
+            members=[$($members)* $name,] fields=[$($fields)* $name: $type,]
+            readers=[$($readers)* name=$name, type=$type {$(width=$width)? $(separator=[$($separator)+])? $(tuple=$key_value_sep)? $(tuple=$tuple_sep)? $(validate=($($validation)+))?},]
+            writers=[$($writers)* value=($name) {$(separator=[$($separator)+])? $(tuple=$key_value_sep)? $(tuple=$tuple_sep)?},]
+            rest=[$($($rest)*)?]
+        }
+    };
+    (
+        @fields then=$then:tt leading=$leading:tt
+        members=[$($members:tt)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+        rest=[
+            $(#[separated_by $($separator:tt)+])?
+            $(#[key_value_sep = $key_value_sep:literal])?
+            $(#[tuple_sep = $tuple_sep:literal])?
+            $(#[width = $width:literal])?
+            $(#[validate($($validation:tt)+)])?
+            _ : $type:ty $(= $display:literal)?, $lit:literal $(, $($rest:tt)*)?
+        ]
+    ) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=$leading
+            members=[$($members)*] fields=[$($fields)*]
+            readers=[$($readers)* name=_, type=$type {$(width=$width)? until=$lit $(separator=[$($separator)+])? $(tuple=$key_value_sep)? $(tuple=$tuple_sep)? $(validate=($($validation)+))?},]
+            writers=[$($writers)* value=($crate::ignored_field_text!($type $(, $display)?)) {until=$lit},]
+            rest=[$($($rest)*)?]
+        }
+    };
+    (
+        @fields then=$then:tt leading=$leading:tt
+        members=[$($members:tt)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+        rest=[
+            $(#[separated_by $($separator:tt)+])?
+            $(#[key_value_sep = $key_value_sep:literal])?
+            $(#[tuple_sep = $tuple_sep:literal])?
+            $(#[width = $width:literal])?
+            $(#[validate($($validation:tt)+)])?
+            _ : $type:ty $(= $display:literal)? $(, $($rest:tt)*)?
+        ]
+    ) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=$leading
+            members=[$($members)*] fields=[$($fields)*]
+            readers=[$($readers)* name=_, type=$type {$(width=$width)? $(separator=[$($separator)+])? $(tuple=$key_value_sep)? $(tuple=$tuple_sep)? $(validate=($($validation)+))?},]
+            writers=[$($writers)* value=($crate::ignored_field_text!($type $(, $display)?)) {},]
+            rest=[$($($rest)*)?]
+        }
+    };
+    // A bare `_` reads up to the next literal, or the rest of the text.
+    (
+        @fields then=$then:tt leading=$leading:tt
+        members=[$($members:tt)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+        rest=[_, $lit:literal $(, $($rest:tt)*)?]
+    ) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=$leading
+            members=[$($members)*] fields=[$($fields)*]
+            readers=[$($readers)* name=_, type=&str {until=$lit},]
+            writers=[$($writers)* value=(&"") {until=$lit},]
+            rest=[$($($rest)*)?]
+        }
+    };
+    (
+        @fields then=$then:tt leading=$leading:tt
+        members=[$($members:tt)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($writers:tt)*]
+        rest=[_ $(,)?]
+    ) => {
+        $crate::formatted_struct!{
+            @fields then=$then leading=$leading
+            members=[$($members)*] fields=[$($fields)*]
+            readers=[$($readers)* name=_, type=&str {},]
+            writers=[$($writers)* value=(&"") {},]
+            rest=[]
+        }
+    };
+    (
+        @fields then=[$($then:tt)*] leading=$leading:tt
+        members=$members:tt fields=$fields:tt readers=$readers:tt writers=$writers:tt
+        rest=[]
+    ) => {
+        $crate::formatted_struct!{
+            $($then)* leading=$leading
+            members=$members fields=$fields readers=$readers writers=$writers
+        }
+    };
+    // With `#[longest_match]`, the variant that leaves the least of the text unread wins. It goes
+    // after `#[display]`, if both are there.
+    (
+        #[display]
+        #[longest_match]
         $(#[$($enum_meta:meta),*])?
-        $vis:vis enum $enum_name:ident
+        $vis:vis enum $enum_name:ident $(<$lifetime:lifetime>)?
         {
-            $(
-                $variant_name:ident {
-                    $($leading_literal:literal,)?
-                    $(
-                        $(#[separated_by=$separator:literal])?
-                        $name:ident : $type:ty,
-                        $($lit:literal)?
-                    ),*
-                    $(,)?
-                },
-            )+
+            $($variants:tt)+
         }
     ) => {
-        $crate::make_item!{
-            enum $enum_name vis=($vis) $(meta=$($enum_meta),*)?
-            {
-                $(
-                    $variant_name
-                    {
-                        $($name:$type),*
-                    }
-                ),+
-            }
+        $crate::formatted_struct!{
+            @enum [$(#[$($enum_meta),*])?] [$vis] $enum_name [$($lifetime)?] f [true display]
+            variants=[] parsers=[] writers=[] rest=[$($variants)+]
+        }
+    };
+    (
+        #[display]
+        $(#[$($enum_meta:meta),*])?
+        $vis:vis enum $enum_name:ident $(<$lifetime:lifetime>)?
+        {
+            $($variants:tt)+
+        }
+    ) => {
+        $crate::formatted_struct!{
+            @enum [$(#[$($enum_meta),*])?] [$vis] $enum_name [$($lifetime)?] f [false display]
+            variants=[] parsers=[] writers=[] rest=[$($variants)+]
+        }
+    };
+    (
+        #[longest_match]
+        $(#[$($enum_meta:meta),*])?
+        $vis:vis enum $enum_name:ident $(<$lifetime:lifetime>)?
+        {
+            $($variants:tt)+
+        }
+    ) => {
+        $crate::formatted_struct!{
+            @enum [$(#[$($enum_meta),*])?] [$vis] $enum_name [$($lifetime)?] f [true]
+            variants=[] parsers=[] writers=[] rest=[$($variants)+]
+        }
+    };
+    (
+        $(#[$($enum_meta:meta),*])?
+        $vis:vis enum $enum_name:ident $(<$lifetime:lifetime>)?
+        {
+            $($variants:tt)+
+        }
+    ) => {
+        $crate::formatted_struct!{
+            @enum [$(#[$($enum_meta),*])?] [$vis] $enum_name [$($lifetime)?] f [false]
+            variants=[] parsers=[] writers=[] rest=[$($variants)+]
+        }
+    };
+    // The variants are munched one at a time, as they can have different shapes. Struct variants
+    // munch their fields first, and come back to `@variant`.
+    (
+        @enum $meta:tt $vis:tt $enum_name:ident $lifetime:tt $f:ident $options:tt
+        variants=$variants:tt parsers=$parsers:tt writers=$writers:tt
+        rest=[$variant_name:ident { $($body:tt)* } $(, $($rest:tt)*)?]
+    ) => {
+        $crate::formatted_struct!{
+            @fields_start
+            then=[
+                @variant $meta $vis $enum_name $lifetime $f $options
+                variants=$variants parsers=$parsers writers=$writers
+                rest=[$($($rest)*)?] $variant_name
+            ]
+            rest=[$($body)*]
         }
-        impl $crate::Parsable for $enum_name {
-            fn parse(text: &str) -> MyResult<$enum_name> {
+    };
+    (
+        @variant $meta:tt $vis:tt $enum_name:ident $lifetime:tt $f:ident $options:tt
+        variants=[$($variants:tt)*] parsers=[$($parsers:tt)*] writers=[$($writers:tt)*]
+        rest=$rest:tt $variant_name:ident
+        leading=[$($leading_literal:literal)?] members=[$($member:ident,)*] fields=[$($fields:tt)*]
+        readers=[$($readers:tt)*] writers=[$($field_writers:tt)*]
+    ) => {
+        $crate::formatted_struct!{
+            @enum $meta $vis $enum_name $lifetime $f $options
+            variants=[$($variants)* $variant_name { $($fields)* },]
+            parsers=[$($parsers)* ($variant_name, |text| -> Result<(Self, usize), $crate::ParseError> {
                 use $enum_name::*;
-                let errors = [
-                    $(
-                        {
-                            let result = (|| -> MyResult<$enum_name> {
-                                $crate::make_reader_body!{
-                                    constructor=$variant_name
-                                    text=(text)
-                                    $(leading_literal=$leading_literal)?
-                                    $(name=$name, type=$type {$(until=$lit)? $(separator=$separator)?}),*
-                                }
-                            })();
-                            match result {
-                                Ok(x) => return Ok(x),
-                                Err(e) => e,
-                            }
-                        },
-                    )+
-                ];
-                
-                let mut error_text = concat!("Could not find a match for enum ", stringify!($enum_name), "\n").to_string();
-                for error in errors {
-                    error_text.push_str("    ");
-                    error_text.push_str(&error.to_string());
-                    error_text.push_str("\n");
+                $crate::make_reader_body!{
+                    constructor=$variant_name
+                    text=(text)
+                    returning=with_unread
+                    $(leading_literal=$leading_literal)?
+                    members=[$($member),*]
+                    $($readers)*
+                }
+            }),]
+            writers=[$($writers)* $enum_name::$variant_name { $($member),* } => {
+                $crate::make_writer_body!{
+                    formatter=$f
+                    $(leading_literal=$leading_literal)?
+                    $($field_writers)*
+                }
+            },]
+            rest=$rest
+        }
+    };
+    (
+        @enum $meta:tt $vis:tt $enum_name:ident $lifetime:tt $f:ident $options:tt
+        variants=[$($variants:tt)*] parsers=[$($parsers:tt)*] writers=[$($writers:tt)*]
+        rest=[$variant_name:ident($type:ty) $(, $($rest:tt)*)?]
+    ) => {
+        $crate::formatted_struct!{
+            @enum $meta $vis $enum_name $lifetime $f $options
+            variants=[$($variants)* $variant_name($type),]
+            parsers=[$($parsers)* ($variant_name, |text| -> Result<(Self, usize), $crate::ParseError> {
+                $crate::parse_single!(type=$type, str=text).map(|value| ($enum_name::$variant_name(value), 0))
+            }),]
+            writers=[$($writers)* $enum_name::$variant_name(value) => write!($f, "{}", value),]
+            rest=[$($($rest)*)?]
+        }
+    };
+    // Unlike the literals between fields, a unit variant's literal has to match the whole text.
+    (
+        @enum $meta:tt $vis:tt $enum_name:ident $lifetime:tt $f:ident $options:tt
+        variants=[$($variants:tt)*] parsers=[$($parsers:tt)*] writers=[$($writers:tt)*]
+        rest=[$variant_name:ident = $lit:literal $(, $($rest:tt)*)?]
+    ) => {
+        $crate::formatted_struct!{
+            @enum $meta $vis $enum_name $lifetime $f $options
+            variants=[$($variants)* $variant_name,]
+            parsers=[$($parsers)* ($variant_name, |text| -> Result<(Self, usize), $crate::ParseError> {
+                if $crate::make_regex!(concat!("^(?:", $lit, ")$")).is_match(text) {
+                    Ok(($enum_name::$variant_name, 0))
+                } else {
+                    let kind = $crate::ParseErrorKind::SkipNotFound { pattern: $lit.to_string() };
+                    Err($crate::ParseError::new(kind, text))
+                }
+            }),]
+            writers=[$($writers)* $enum_name::$variant_name => {
+                write!($f, "{}", $crate::make_canonical_text!($lit))
+            },]
+            rest=[$($($rest)*)?]
+        }
+    };
+    (
+        @enum [$($meta:tt)*] [$vis:vis] $enum_name:ident [$($lifetime:lifetime)?] $f:ident
+        [$longest_match:tt $($display:ident)?]
+        variants=[$($variants:tt)*] parsers=[$(($variant_name:ident, $parser:expr),)+]
+        writers=[$($writers:tt)*] rest=[]
+    ) => {
+        $($meta)*
+        $vis enum $enum_name $(<$lifetime>)? {
+            $($variants)*
+        }
+
+        // The parsers are closures without a type for `text`, so that they get its lifetime.
+        impl<'parse_text $(: $lifetime, $lifetime)?> $crate::Parsable<'parse_text> for $enum_name $(<$lifetime>)? {
+            fn parse(text: &'parse_text str) -> Result<Self, $crate::ParseError> {
+                $crate::parse_variants(
+                    text,
+                    $longest_match,
+                    &[$((stringify!($variant_name), &$parser),)+],
+                )
+                .map_err(|e| e.with_root(stringify!($enum_name)))
+            }
+        }
+
+        $crate::formatted_struct!{
+            @display [$($display)?] $enum_name [$($lifetime)?] self $f {
+                match self {
+                    $($writers)*
                 }
-                Err(From::from(error_text))
             }
         }
     };
 }
 
+#[cfg(test)]
 mod tests {
 
     formatted_struct! {
+        #[display]
         #[derive(PartialEq, Eq, Debug)]
         pub struct TestLeadingInnerAndTrailing {
             "game",
@@ -179,6 +654,7 @@ mod tests {
     }
 
     formatted_struct! {
+        #[display]
         #[derive(PartialEq, Eq, Debug)]
         enum VariantTest {
             Foo {
@@ -195,10 +671,168 @@ mod tests {
         }
     }
 
-    #[allow(unused_imports)]
-    use super::*;
-    #[allow(unused_imports)]
-    use crate::Parsable;
+    // `Name` also parses `a=3`, but leaves the `3` unread.
+    formatted_struct! {
+        #[longest_match]
+        #[derive(PartialEq, Eq, Debug)]
+        enum LongestTest {
+            Name {
+                name: String,
+                "=",
+            },
+            Set {
+                name: String,
+                "=",
+                value: i32,
+            },
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        struct TestNested {
+            "blocks:",
+            #[separated_by("\n\n", "\n", ", *")]
+            blocks: Vec<Vec<Vec<i32>>>,
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        struct TestKeyValues {
+            #[separated_by = "\n"]
+            #[key_value_sep = ": "]
+            values: std::collections::BTreeMap<String, i32>,
+            "\n\n",
+            #[separated_by = ","]
+            pair: [i32; 2],
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        struct TestTuples {
+            #[tuple_sep = ","]
+            start: (i32, i32),
+            " -> ",
+            #[separated_by = " "]
+            #[tuple_sep = ","]
+            path: Vec<(i32, i32)>,
+            ";",
+            label: Option<String>,
+            "!",
+            boxed: Box<i32>,
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        struct TestBorrowed<'a> {
+            from: &'a str,
+            "-",
+            #[separated_by = ","]
+            to: Vec<&'a str>,
+        }
+    }
+
+    formatted_struct! {
+        #[derive(PartialEq, Eq, Debug)]
+        enum BorrowedVariants<'a> {
+            Number(i32),
+            Word(&'a str),
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        struct TestWidths {
+            #[width = 2]
+            code: String,
+            #[width = 1]
+            digit: u8,
+            ":",
+            digits: crate::Digits,
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        enum MixedVariants {
+            Or = "OR",
+            Xor = "XOR",
+            Number(i32),
+            Pair {
+                first: i32,
+                ",",
+                second: i32,
+            }
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        struct TestIgnored {
+            _,
+            "\nRegister ",
+            _,
+            ": ",
+            register: i64,
+            "\nid=",
+            #[validate(range = 1..10)]
+            _: u8 = "1",
+            ";",
+            #[separated_by = ","]
+            values: Vec<i32>,
+        }
+    }
+
+    formatted_struct! {
+        #[display]
+        #[derive(PartialEq, Eq, Debug)]
+        enum IgnoredVariants {
+            Labeled {
+                _,
+                ": ",
+                value: i32,
+            },
+            Trailing {
+                value: i32,
+                " ",
+                _: char = "y",
+            },
+        }
+    }
+
+    // Without `#[display]`, the fields don't need to be written, nor the ignored ones given a text.
+    #[derive(Debug, PartialEq)]
+    struct Unwritable(i32);
+
+    impl From<i32> for Unwritable {
+        fn from(value: i32) -> Unwritable {
+            Unwritable(value)
+        }
+    }
+
+    crate::try_from_parsable!(Unwritable, i32);
+
+    formatted_struct! {
+        #[derive(Debug)]
+        struct TestWithoutDisplay {
+            "id=",
+            id: Unwritable,
+            ";",
+            _: u8,
+        }
+    }
+
+    use crate::{MyResult, Parsable, ParseErrorKind};
 
     #[test]
     fn parse_leading_inner_and_trailing() -> MyResult<()> {
@@ -238,6 +872,23 @@ mod tests {
         Ok(())
     }
     #[test]
+    fn parse_longest_match() -> MyResult<()> {
+        assert_eq!(
+            LongestTest::parse("a=3")?,
+            LongestTest::Set {
+                name: "a".to_string(),
+                value: 3
+            }
+        );
+        assert_eq!(
+            LongestTest::parse("a=x")?,
+            LongestTest::Name {
+                name: "a".to_string()
+            }
+        );
+        Ok(())
+    }
+    #[test]
     fn parse_enum_variant_2() -> MyResult<()> {
         let parsed = VariantTest::parse("fiz15buz")?;
         assert_eq!(parsed, VariantTest::Fiz { buz: 15 });
@@ -246,9 +897,146 @@ mod tests {
     #[test]
     fn parse_enum_err() {
         let parsed = VariantTest::parse("faz").unwrap_err();
+        let ParseErrorKind::NoVariantMatched { errors } = parsed.kind() else {
+            panic!("unexpected error {}", parsed);
+        };
+        let skipped_patterns = errors
+            .iter()
+            .map(|error| match error.kind() {
+                ParseErrorKind::SkipNotFound { pattern } => (error.path(), pattern.as_str()),
+                _ => panic!("unexpected error {}", error),
+            })
+            .collect::<Vec<_>>();
         assert_eq!(
-            parsed.to_string(), 
-            "Could not find a match for enum VariantTest\n    skip didn't find Regex(\"foo\") when searching in \"faz\" as part of \"faz\"\n    skip didn't find Regex(\"fiz\") when searching in \"faz\" as part of \"faz\"\n"
+            skipped_patterns,
+            vec![("Foo".to_string(), "foo"), ("Fiz".to_string(), "fiz")]
         );
     }
+    #[test]
+    fn parse_unit_and_tuple_variants() -> MyResult<()> {
+        let pair = MixedVariants::Pair {
+            first: 1,
+            second: 2,
+        };
+        for (text, expected) in [
+            ("XOR", MixedVariants::Xor),
+            ("OR", MixedVariants::Or),
+            ("12", MixedVariants::Number(12)),
+            ("1,2", pair),
+        ] {
+            let parsed = MixedVariants::parse(text)?;
+            assert_eq!(parsed.to_string(), text);
+            assert_eq!(parsed, expected);
+        }
+        assert!(MixedVariants::parse("ORX").is_err());
+        Ok(())
+    }
+    #[test]
+    fn display_round_trips() -> MyResult<()> {
+        let parsed = TestLeadingInnerAndTrailing::parse("gamef00bz1,2,3bar123baz")?;
+        assert_eq!(parsed.to_string(), "gamef00bz1,2,3bar123baz");
+        assert_eq!(
+            TestLeadingInnerAndTrailing::parse(&parsed.to_string())?,
+            parsed
+        );
+        let parsed = VariantTest::parse("foob@rbaz3")?;
+        assert_eq!(VariantTest::parse(&parsed.to_string())?, parsed);
+        Ok(())
+    }
+    #[test]
+    fn parse_nested_separators() -> MyResult<()> {
+        let parsed = TestNested::parse("blocks:1,2\n3\n\n4,  5")?;
+        assert_eq!(
+            parsed.blocks,
+            vec![vec![vec![1, 2], vec![3]], vec![vec![4, 5]]]
+        );
+        assert_eq!(parsed.to_string(), "blocks:1,2\n3\n\n4,5");
+        let error = TestNested::parse("blocks:1\n\n2\n3,x").unwrap_err();
+        assert_eq!(error.path(), "TestNested.blocks[1][1][1]");
+        Ok(())
+    }
+    #[test]
+    fn parse_maps_and_arrays() -> MyResult<()> {
+        let text = "a: 1\nb: 2\n\n3,4";
+        let parsed = TestKeyValues::parse(text)?;
+        assert_eq!(parsed.values["b"], 2);
+        assert_eq!(parsed.pair, [3, 4]);
+        assert_eq!(parsed.to_string(), text);
+        let error = TestKeyValues::parse("a: 1\nb 2\n\n3,4").unwrap_err();
+        assert_eq!(error.path(), "TestKeyValues.values[1]");
+        Ok(())
+    }
+    #[test]
+    fn parse_tuples_and_options() -> MyResult<()> {
+        let text = "1,2 -> 3,4 5,-6;!7";
+        let parsed = TestTuples::parse(text)?;
+        assert_eq!(parsed.start, (1, 2));
+        assert_eq!(parsed.path, vec![(3, 4), (5, -6)]);
+        assert_eq!(parsed.label, None);
+        assert_eq!(parsed.boxed, Box::new(7));
+        assert_eq!(parsed.to_string(), text);
+        let text = "1,2 -> 3,4;end!7";
+        assert_eq!(TestTuples::parse(text)?.label.as_deref(), Some("end"));
+        assert_eq!(TestTuples::parse(text)?.to_string(), text);
+        let error = TestTuples::parse("1,2 -> 3,4 5;!7").unwrap_err();
+        assert_eq!(error.path(), "TestTuples.path[1]");
+        Ok(())
+    }
+    #[test]
+    fn parse_widths() -> MyResult<()> {
+        let text = "ab7:0123";
+        let parsed = TestWidths::parse(text)?;
+        assert_eq!(parsed.code, "ab");
+        assert_eq!(parsed.digit, 7);
+        assert_eq!(parsed.digits.0, vec![0, 1, 2, 3]);
+        assert_eq!(parsed.to_string(), text);
+        let error = TestWidths::parse("a").unwrap_err();
+        assert_eq!(error.path(), "TestWidths.code");
+        assert!(matches!(error.kind(), ParseErrorKind::TooShort { .. }));
+        Ok(())
+    }
+    #[test]
+    fn parse_borrowed() -> MyResult<()> {
+        let text = "ab-cd,ef";
+        let parsed = TestBorrowed::parse(text)?;
+        assert_eq!(parsed.from, "ab");
+        assert_eq!(parsed.to, vec!["cd", "ef"]);
+        assert!(std::ptr::eq(parsed.to[1].as_ptr(), text[6..].as_ptr()));
+        assert_eq!(parsed.to_string(), text);
+        // `12` is also a `Word`, which only `strict_parsing` notices.
+        if cfg!(feature = "strict_parsing") {
+            assert!(BorrowedVariants::parse("12").is_err());
+        } else {
+            assert_eq!(BorrowedVariants::parse("12")?, BorrowedVariants::Number(12));
+        }
+        assert_eq!(BorrowedVariants::parse("ab")?, BorrowedVariants::Word("ab"));
+        Ok(())
+    }
+    #[test]
+    fn parse_ignored_fields() -> MyResult<()> {
+        let parsed = TestIgnored::parse("Header line\nRegister A: 729\nid=3;1,2")?;
+        assert_eq!(
+            parsed,
+            TestIgnored {
+                register: 729,
+                values: vec![1, 2]
+            }
+        );
+        assert_eq!(parsed.to_string(), "\nRegister : 729\nid=1;1,2");
+        assert_eq!(TestIgnored::parse(&parsed.to_string())?, parsed);
+        let error = TestIgnored::parse("h\nRegister A: 1\nid=12;1").unwrap_err();
+        assert_eq!(error.path(), "TestIgnored._");
+        assert!(matches!(error.kind(), ParseErrorKind::Invalid { .. }));
+        for (text, expected) in [
+            ("x: 3", IgnoredVariants::Labeled { value: 3 }),
+            ("4 y", IgnoredVariants::Trailing { value: 4 }),
+        ] {
+            let parsed = IgnoredVariants::parse(text)?;
+            assert_eq!(IgnoredVariants::parse(&parsed.to_string())?, parsed);
+            assert_eq!(parsed, expected);
+        }
+        assert!(IgnoredVariants::parse("4 yz").is_err());
+        assert_eq!(TestWithoutDisplay::parse("id=3;7")?.id, Unwritable(3));
+        Ok(())
+    }
 }
@@ -0,0 +1,231 @@
+use std::{
+    any::Any,
+    fmt::Debug,
+    panic::{self, catch_unwind, AssertUnwindSafe},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{DaySolution, ExistentialDaySolution, MyResult, Parsable};
+
+// Another implementation of a part of the day `A`, which `fuzz` checks against the main one.
+pub struct Variant<A: DaySolution> {
+    pub name: &'static str,
+    #[allow(clippy::type_complexity)]
+    solve: Box<dyn Fn(&A::InputFormat<'_>) -> MyResult<String>>,
+}
+
+impl<A: DaySolution> Variant<A> {
+    pub fn new<R: Debug + 'static>(
+        name: &'static str,
+        solve: fn(&A::InputFormat<'_>) -> MyResult<R>,
+    ) -> Self {
+        Variant {
+            name,
+            solve: Box::new(move |input| solve(input).map(|answer| format!("{:?}", answer))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckOutcome {
+    Unparsable,
+    Agreed,
+    Failed(String),
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic>".to_string()
+    }
+}
+
+fn check_part<A: DaySolution>(
+    input: &A::InputFormat<'_>,
+    part: i32,
+    variants: Vec<Variant<A>>,
+) -> Option<String> {
+    let mut answers = Vec::new();
+    for variant in variants {
+        match catch_unwind(AssertUnwindSafe(|| (variant.solve)(input))) {
+            Ok(answer) => answers.push((
+                variant.name,
+                answer.unwrap_or_else(|e| format!("error: {}", e)),
+            )),
+            Err(payload) => {
+                return Some(format!(
+                    "Part {}: {} panicked: {}",
+                    part,
+                    variant.name,
+                    panic_message(payload)
+                ))
+            }
+        }
+    }
+    if answers.iter().all(|(_, answer)| *answer == answers[0].1) {
+        return None;
+    }
+    let answers = answers
+        .iter()
+        .map(|(name, answer)| format!("{} = {}", name, answer))
+        .collect::<Vec<_>>();
+    Some(format!("Part {}: {}", part, answers.join(", ")))
+}
+
+// Runs every variant of both parts on `text` and reports the first disagreement or panic.
+pub(crate) fn check_variants<A: DaySolution>(text: &str) -> CheckOutcome
+where
+    for<'a> A::InputFormat<'a>: Parsable<'a>,
+{
+    let text = A::normalization().normalize(text);
+    let parsed = catch_unwind(|| A::InputFormat::parse(&text));
+    let input = match parsed {
+        Ok(Ok(input)) => input,
+        Ok(Err(_)) => return CheckOutcome::Unparsable,
+        Err(payload) => {
+            return CheckOutcome::Failed(format!("parsing panicked: {}", panic_message(payload)))
+        }
+    };
+    // `solve_1` and `solve_2` don't fit `Variant::new`, as they aren't `fn` pointers for every input lifetime.
+    let mut variants_1 = vec![Variant {
+        name: "solve_1",
+        solve: Box::new(|input| A::solve_1(input).map(|answer| format!("{:?}", answer))),
+    }];
+    variants_1.extend(A::variants_1());
+    let mut variants_2 = vec![Variant {
+        name: "solve_2",
+        solve: Box::new(|input| A::solve_2(input).map(|answer| format!("{:?}", answer))),
+    }];
+    variants_2.extend(A::variants_2());
+    check_part(&input, 1, variants_1)
+        .or_else(|| check_part(&input, 2, variants_2))
+        .map_or(CheckOutcome::Agreed, CheckOutcome::Failed)
+}
+
+fn mutate(text: &str, rng: &mut StdRng) -> String {
+    let mut lines = text.lines().map(str::to_string).collect::<Vec<_>>();
+    let alphabet = text.chars().filter(|c| *c != '\n').collect::<Vec<_>>();
+    for _ in 0..rng.gen_range(1..=3) {
+        if lines.is_empty() {
+            break;
+        }
+        let i = rng.gen_range(0..lines.len());
+        match rng.gen_range(0..4) {
+            0 => {
+                lines.remove(i);
+            }
+            1 => lines.insert(i, lines[i].clone()),
+            2 => {
+                let j = rng.gen_range(0..lines.len());
+                lines.swap(i, j);
+            }
+            _ => {
+                let mut line = lines[i].chars().collect::<Vec<_>>();
+                if let (false, Some(&replacement)) = (line.is_empty(), alphabet.choose(rng)) {
+                    let j = rng.gen_range(0..line.len());
+                    line[j] = replacement;
+                }
+                lines[i] = line.into_iter().collect();
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+// Greedily drops ever smaller chunks of lines while the input keeps failing.
+fn minimize(text: &str, check: impl Fn(&str) -> CheckOutcome) -> String {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let mut chunk_size = lines.len() / 2;
+    while chunk_size > 0 {
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk_size).min(lines.len());
+            let candidate = [&lines[..start], &lines[end..]].concat();
+            if matches!(check(&candidate.join("\n")), CheckOutcome::Failed(_)) {
+                lines = candidate;
+            } else {
+                start = end;
+            }
+        }
+        chunk_size /= 2;
+    }
+    lines.join("\n")
+}
+
+// Panics are expected and reported by `check_variants`, so they shouldn't also be printed.
+fn without_panic_output<R>(computation: impl FnOnce() -> R) -> R {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = computation();
+    panic::set_hook(default_hook);
+    result
+}
+
+// Mutates `base_input` when given, otherwise uses the day's generator.
+pub(crate) fn fuzz(
+    day_solution: &ExistentialDaySolution,
+    base_input: Option<&str>,
+    seed: u64,
+    size: usize,
+    iterations: usize,
+) -> MyResult<()> {
+    let check = day_solution.check_variants;
+    let mut unparsable = 0;
+    for iteration in 0..iterations {
+        let iteration_seed = seed.wrapping_add(iteration as u64);
+        let input = match base_input {
+            Some(base_input) => mutate(base_input, &mut StdRng::seed_from_u64(iteration_seed)),
+            None => (day_solution.generate)(iteration_seed, size)?,
+        };
+        match without_panic_output(|| check(&input)) {
+            CheckOutcome::Unparsable => unparsable += 1,
+            CheckOutcome::Agreed => {}
+            CheckOutcome::Failed(failure) => {
+                println!("Input #{} failed: {}", iteration, failure);
+                let minimized = without_panic_output(|| minimize(&input, check));
+                println!(
+                    "Minimized from {} to {} lines:\n{}",
+                    input.lines().count(),
+                    minimized.lines().count(),
+                    minimized
+                );
+                if let CheckOutcome::Failed(failure) = without_panic_output(|| check(&minimized)) {
+                    println!("which fails with: {}", failure);
+                }
+                return Err(From::from("found a failing input"));
+            }
+        }
+    }
+    println!(
+        "All variants agreed on {} inputs ({} unparsable)",
+        iterations - unparsable,
+        unparsable
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimize_keeps_failing_lines() {
+        let text = (0..20)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let check = |text: &str| {
+            let lines = text.lines().collect::<Vec<_>>();
+            if lines.contains(&"7") && lines.contains(&"13") {
+                CheckOutcome::Failed("7 and 13".to_string())
+            } else {
+                CheckOutcome::Agreed
+            }
+        };
+        assert_eq!(minimize(&text, check), "7\n13");
+    }
+}
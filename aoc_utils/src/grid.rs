@@ -0,0 +1,241 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{Parsable, ParseError, ParseErrorKind};
+
+// (row, column), signed so that neighbors of the edge cells can be computed and rejected.
+pub type Point = (isize, isize);
+
+// Up, right, down, left, so turning right is going to the next index.
+pub const DIRECTIONS_4: [Point; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+// Clockwise, starting from up.
+pub const DIRECTIONS_8: [Point; 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![fill; height * width],
+        }
+    }
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, ParseError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(row) = rows.iter().position(|row| row.len() != width) {
+            return Err(ParseErrorKind::UnevenRows {
+                row,
+                length: rows[row].len(),
+                expected: width,
+            }
+            .into());
+        }
+        Ok(Grid {
+            height,
+            width,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn contains(&self, (i, j): Point) -> bool {
+        (0..self.height as isize).contains(&i) && (0..self.width as isize).contains(&j)
+    }
+    fn cell_index(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.0 as usize * self.width + point.1 as usize)
+    }
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cell_index(point).map(|index| &self.cells[index])
+    }
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cell_index(point).map(|index| &mut self.cells[index])
+    }
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (height, width) = (self.height as isize, self.width as isize);
+        (0..height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+    fn neighbors<'a>(
+        &'a self,
+        (i, j): Point,
+        directions: &'a [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .map(move |(di, dj)| (i + di, j + dj))
+            .filter(|&point| self.contains(point))
+    }
+    // The neighbors that are inside the grid, in `DIRECTIONS_4` order.
+    pub fn neighbors_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &DIRECTIONS_4)
+    }
+    // The neighbors that are inside the grid, in `DIRECTIONS_8` order.
+    pub fn neighbors_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &DIRECTIONS_8)
+    }
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.cells[i * self.width..(i + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|i| self.row(i))
+    }
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(j < self.width, "column {} of a {} wide grid", j, self.width);
+        self.cells[j..].iter().step_by(self.width)
+    }
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        let (height, width) = (self.height, self.width);
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", point, height, width))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", point, height, width))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Each char is parsed as a separate `T`.
+impl<'a, T: Parsable<'a>> Parsable<'a> for Grid<T> {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        let lines = text.lines().collect::<Vec<_>>();
+        let rows = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                line.char_indices()
+                    .enumerate()
+                    .map(|(j, (start, c))| {
+                        T::parse(&line[start..start + c.len_utf8()])
+                            .map_err(|e| e.with_index(j).with_index(i))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Grid::from_rows(rows).map_err(|e| match e.kind() {
+            &ParseErrorKind::UnevenRows { row, .. } => e.at(lines[row]),
+            _ => e,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() -> Result<(), ParseError> {
+        let grid = Grid::<char>::parse("#.S\n..#")?;
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid.find(&'S'), Some((0, 2)));
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            vec![(0, 0), (1, 2)]
+        );
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.column(2).collect::<String>(), "S#");
+        assert_eq!(grid.to_string(), "#.S\n..#");
+        Ok(())
+    }
+    #[test]
+    fn parse_errors() {
+        let input = "ab\nc";
+        let error = Grid::<char>::parse(input).unwrap_err().locate(input);
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::UnevenRows {
+                row: 1,
+                length: 1,
+                expected: 2
+            }
+        );
+        assert_eq!(error.line_and_column(), Some((2, 1)));
+        let input = "12\n3x";
+        let error = Grid::<u8>::parse(input).unwrap_err().locate(input);
+        assert_eq!(error.path(), "[1][1]");
+        assert_eq!(error.line_and_column(), Some((2, 2)));
+    }
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbors_4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors_8((1, 1)).count(), 8);
+    }
+}
@@ -8,3 +8,11 @@ pub fn make_real_path(day_name: i32) -> String {
     assert!((0..100).contains(&day_name));
     format!("inputs/real/{:02}.txt", day_name)
 }
+
+pub fn make_generated_path(day_name: i32, seed: u64, size: usize) -> String {
+    assert!((0..100).contains(&day_name));
+    format!(
+        "inputs/generated/{:02}.seed_{}.size_{}.txt",
+        day_name, seed, size
+    )
+}
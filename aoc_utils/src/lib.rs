@@ -1,18 +1,55 @@
 use std::error::Error;
 
+// So that the code `#[derive(Parsable)]` generates also works inside this crate.
+extern crate self as aoc_utils;
+
 mod aoc_main;
+mod cancellation;
+mod canonical_text;
+mod captures;
+mod char_enum;
 mod day_solution;
 mod format_struct;
+mod fuzz;
 pub mod graph;
+mod grid;
 mod input_paths;
 mod memo;
+mod normalization;
+mod parallel;
 mod parsable;
+mod parse_error;
+mod pattern;
+mod progress;
 mod register_days;
+mod separated;
+mod validation;
+mod variants;
 
 pub use crate::aoc_main::aoc_main;
+pub use crate::cancellation::{cancellation_token, check_cancelled, CancellationToken, Cancelled};
+pub use crate::canonical_text::{
+    canonical_text, write_separated, FieldWriter, WriteField, WriteOptionalField, WriteSeparated,
+    WriteTuple,
+};
+pub use crate::captures::{CapturesParsable, Matches};
 pub use crate::day_solution::{make_day_solution, DaySolution, ExistentialDaySolution};
-pub use crate::input_paths::{make_real_path, make_sample_path};
+pub use crate::fuzz::Variant;
+pub use crate::grid::{Grid, Point, DIRECTIONS_4, DIRECTIONS_8};
+pub use crate::input_paths::{make_generated_path, make_real_path, make_sample_path};
 pub use crate::memo::make_recursive_fn;
-pub use crate::parsable::{Chars, Parsable, ParseBuffer, SeparatorParsable};
-pub use regex::Regex;
+pub use crate::normalization::Normalization;
+pub use crate::parallel::parallel_fold;
+pub use crate::parsable::{
+    parse_from_str, parse_input, parse_try_from, Chars, Digits, FromStrParsable, Parsable,
+    ParseBuffer, SeparatorParsable, TupleParsable,
+};
+pub use crate::parse_error::{ParseError, ParseErrorKind};
+pub use crate::pattern::{Pattern, Split};
+pub use crate::progress::Progress;
+pub use crate::separated::{Separated, SeparatedIter};
+pub use crate::validation::{check_range, validated};
+pub use crate::variants::{parse_variants, VariantParser};
+pub use aoc_utils_derive::Parsable;
+pub use regex::{Captures, Regex};
 pub type MyResult<T> = Result<T, Box<dyn Error>>;
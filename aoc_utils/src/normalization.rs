@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+
+// How the runner cleans up an input file before parsing it, chosen per day with
+// `DaySolution::normalization`. Inputs saved on Windows end their lines with `\r\n`, which breaks
+// every `"\n"` separator, and editors like to add a BOM or trailing spaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    pub strip_bom: bool,
+    pub crlf_to_lf: bool,
+    // Spaces and tabs at the end of each line.
+    pub trim_line_ends: bool,
+    // Newlines at the end of the input, which the puzzles never need.
+    pub trim_trailing_newlines: bool,
+}
+
+impl Normalization {
+    pub const ALL: Normalization = Normalization {
+        strip_bom: true,
+        crlf_to_lf: true,
+        trim_line_ends: true,
+        trim_trailing_newlines: true,
+    };
+    // For days where every char of the input matters.
+    pub const NONE: Normalization = Normalization {
+        strip_bom: false,
+        crlf_to_lf: false,
+        trim_line_ends: false,
+        trim_trailing_newlines: false,
+    };
+
+    // Only copies the text when a line has to change.
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let text = match text.strip_prefix('\u{feff}') {
+            Some(rest) if self.strip_bom => rest,
+            _ => text,
+        };
+        let mut text = if text
+            .split('\n')
+            .any(|line| self.normalize_line(line).len() < line.len())
+        {
+            let lines = text.split('\n').map(|line| self.normalize_line(line));
+            Cow::Owned(lines.collect::<Vec<_>>().join("\n"))
+        } else {
+            Cow::Borrowed(text)
+        };
+        if self.trim_trailing_newlines {
+            match &mut text {
+                Cow::Borrowed(text) => *text = text.trim_end_matches('\n'),
+                Cow::Owned(text) => text.truncate(text.trim_end_matches('\n').len()),
+            }
+        }
+        text
+    }
+
+    fn normalize_line<'a>(&self, mut line: &'a str) -> &'a str {
+        if self.crlf_to_lf {
+            line = line.strip_suffix('\r').unwrap_or(line);
+        }
+        if self.trim_line_ends {
+            line = line.trim_end_matches([' ', '\t']);
+        }
+        line
+    }
+}
+
+// Only the changes that can't matter to a puzzle. Days that want the line ends trimmed opt in.
+impl Default for Normalization {
+    fn default() -> Normalization {
+        Normalization {
+            strip_bom: true,
+            crlf_to_lf: true,
+            trim_line_ends: false,
+            trim_trailing_newlines: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let text = "\u{feff}1 2 \r\n3\t\r\n\r\n";
+        assert_eq!(Normalization::ALL.normalize(text), "1 2\n3");
+        assert_eq!(Normalization::NONE.normalize(text), text);
+        assert_eq!(Normalization::default().normalize(text), "1 2 \n3\t");
+        let only_crlf = Normalization {
+            crlf_to_lf: true,
+            ..Normalization::NONE
+        };
+        assert_eq!(only_crlf.normalize(text), "\u{feff}1 2 \n3\t\n\n");
+        assert!(matches!(
+            Normalization::ALL.normalize("1\n2\n\n"),
+            Cow::Borrowed("1\n2")
+        ));
+    }
+}
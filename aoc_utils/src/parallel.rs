@@ -0,0 +1,49 @@
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{cancellation::Cancelled, Progress};
+
+// Folds `items` on all available cores, each thread into its own state, which are all returned.
+// Reports progress per item and stops early when the solve is cancelled.
+pub fn parallel_fold<T: Sync, S: Send>(
+    items: &[T],
+    init: impl Fn() -> S + Sync,
+    fold: impl Fn(&mut S, &T) + Sync,
+) -> Result<Vec<S>, Cancelled> {
+    let n_threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let next_index = AtomicUsize::new(0);
+    let progress = Progress::new(items.len());
+    thread::scope(|scope| {
+        let threads = (0..n_threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut state = init();
+                    while let Some(item) = items.get(next_index.fetch_add(1, Ordering::Relaxed)) {
+                        fold(&mut state, item);
+                        progress.inc()?;
+                    }
+                    Ok(state)
+                })
+            })
+            .collect::<Vec<_>>();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_every_item_once() {
+        let items = (1..=1000).collect::<Vec<i64>>();
+        let partial_sums = parallel_fold(&items, || 0, |sum, item| *sum += item).unwrap();
+        assert_eq!(partial_sums.into_iter().sum::<i64>(), 500500);
+    }
+}
@@ -1,91 +1,385 @@
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt::Display,
+    hash::Hash,
+    str::FromStr,
+};
 
-use regex::Regex;
+use crate::{ParseError, ParseErrorKind, Pattern};
 
-use crate::MyResult;
+// `'a` is the lifetime of the text, so that fields like `&'a str` can borrow from it.
+pub trait Parsable<'a>: Sized {
+    fn parse(text: &'a str) -> Result<Self, ParseError>;
+}
 
-pub trait Parsable: Sized {
-    fn parse(text: &str) -> MyResult<Self>;
+// A collection of items separated by a pattern. `parse_item` is `Item::parse` unless the items are
+// collections themselves, which are split further with the next separator. It lives as long as the
+// text, so that `Separated` can keep it to parse the items later.
+pub trait SeparatorParsable<'a>: Sized {
+    type Item;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<Self::Item, ParseError> + 'a,
+    ) -> Result<Self, ParseError>;
+    fn parse_separated_by(text: &'a str, separator: &Pattern) -> Result<Self, ParseError>
+    where
+        Self::Item: Parsable<'a>,
+    {
+        // The function item would name `Self`, which doesn't live for `'a`.
+        #[allow(clippy::redundant_closure)]
+        Self::parse_separated_with(text, separator, |item| Self::Item::parse(item))
+    }
 }
 
-pub trait SeparatorParsable: Sized {
-    fn parse_separated_by(text: &str, separator: &Regex) -> MyResult<Self>;
+// Parses the whole input, so the error can point at a line and column in it.
+pub fn parse_input<'a, A: Parsable<'a>>(text: &'a str) -> Result<A, ParseError> {
+    A::parse(text).map_err(|e| e.locate(text))
 }
 
-impl<A: FromStr> Parsable for A
+// What `Parsable` does for a type implementing `FromStr`. There is no blanket implementation, so
+// that types like `Option` can have their own; `from_str_parsable!` implements it with this.
+pub fn parse_from_str<A: FromStr>(text: &str) -> Result<A, ParseError>
 where
-    <A as FromStr>::Err: 'static + std::error::Error,
+    <A as FromStr>::Err: std::error::Error,
 {
-    fn parse(text: &str) -> MyResult<A> {
-        match Self::from_str(text) {
-            Ok(x) => Ok(x),
-            Err(err) => Err(From::from(format!("Error {} when parsing {}", err, text))),
+    A::from_str(text).map_err(|err| {
+        ParseError::new(
+            ParseErrorKind::FromStr {
+                type_name: std::any::type_name::<A>(),
+                text: text.to_string(),
+                message: err.to_string(),
+            },
+            text,
+        )
+    })
+}
+
+#[macro_export]
+macro_rules! from_str_parsable {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl<'a> $crate::Parsable<'a> for $type {
+                fn parse(text: &'a str) -> Result<Self, $crate::ParseError> {
+                    $crate::parse_from_str(text)
+                }
+            }
+        )*
+    };
+}
+
+from_str_parsable!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String,
+);
+
+// For a `FromStr` type from another crate, which can't be given `from_str_parsable!` outside of
+// that crate: a field `address: FromStrParsable<Ipv4Addr>` parses like one, and `.0` is the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FromStrParsable<A>(pub A);
+
+impl<A: FromStr> Parsable<'_> for FromStrParsable<A>
+where
+    <A as FromStr>::Err: std::error::Error,
+{
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        parse_from_str(text).map(FromStrParsable)
+    }
+}
+
+impl<A: Display> Display for FromStrParsable<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// What `Parsable` does for a type processed from another one: parses the raw type, then converts it
+// with `TryFrom`. A failed conversion is reported at the start of the text, under `type_name`.
+pub fn parse_try_from<'a, R: Parsable<'a>, A: TryFrom<R>>(
+    text: &'a str,
+    type_name: &'static str,
+) -> Result<A, ParseError>
+where
+    A::Error: Into<Box<dyn std::error::Error>>,
+{
+    A::try_from(R::parse(text)?)
+        .map_err(|e| ParseError::from(e.into()).at(text).with_root(type_name))
+}
+
+// `try_from_parsable!(ProcessedInput, InputFormat);` makes `ProcessedInput` parsable through its
+// `TryFrom<InputFormat>`. Types borrowing from the text name its lifetime first:
+// `try_from_parsable!(<'a> Processed<'a>, InputFormat<'a>);`.
+#[macro_export]
+macro_rules! try_from_parsable {
+    ($type:ty, $raw:ty $(,)?) => {
+        impl<'a> $crate::Parsable<'a> for $type {
+            fn parse(text: &'a str) -> Result<Self, $crate::ParseError> {
+                $crate::parse_try_from::<$raw, Self>(text, stringify!($type))
+            }
+        }
+    };
+    (<$lifetime:lifetime> $type:ty, $raw:ty $(,)?) => {
+        impl<$lifetime> $crate::Parsable<$lifetime> for $type {
+            fn parse(text: &$lifetime str) -> Result<Self, $crate::ParseError> {
+                $crate::parse_try_from::<$raw, Self>(text, stringify!($type))
+            }
+        }
+    };
+}
+
+// Borrows the text, for any lifetime it outlives.
+impl<'a: 'b, 'b> Parsable<'a> for &'b str {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        Ok(text)
+    }
+}
+
+// An empty text is `None`.
+impl<'a, A: Parsable<'a>> Parsable<'a> for Option<A> {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        if text.is_empty() {
+            Ok(None)
+        } else {
+            A::parse(text).map(Some)
         }
     }
 }
 
+impl<'a, A: Parsable<'a>> Parsable<'a> for Box<A> {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        A::parse(text).map(Box::new)
+    }
+}
+
 #[derive(Debug)]
 pub struct Chars(pub Vec<char>);
 
-impl Parsable for Chars {
-    fn parse(text: &str) -> MyResult<Self> {
-        Ok(Chars(String::parse(text)?.chars().collect::<Vec<_>>()))
+impl Parsable<'_> for Chars {
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        Ok(Chars(text.chars().collect::<Vec<_>>()))
+    }
+}
+
+impl Display for Chars {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+// One decimal digit per char, for dense inputs like day 9's disk map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digits(pub Vec<u8>);
+
+const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+impl Parsable<'_> for Digits {
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        text.char_indices()
+            .enumerate()
+            .map(|(i, (start, c))| {
+                c.to_digit(10).map(|digit| digit as u8).ok_or_else(|| {
+                    let kind = ParseErrorKind::InvalidChar {
+                        type_name: "digit",
+                        found: c.to_string(),
+                        valid_chars: &DIGITS,
+                    };
+                    ParseError::new(kind, &text[start..]).with_index(i)
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Digits)
+    }
+}
+
+impl Display for Digits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|digit| write!(f, "{}", digit))
+    }
+}
+
+fn parse_items<'a, A, C: FromIterator<A>>(
+    text: &'a str,
+    separator: &Pattern,
+    parse_item: impl Fn(&'a str) -> Result<A, ParseError>,
+) -> Result<C, ParseError> {
+    separator
+        .split(text)
+        .enumerate()
+        .map(|(i, item)| parse_item(item).map_err(|e| e.at(item).with_index(i)))
+        .collect()
+}
+
+impl<'a, A> SeparatorParsable<'a> for Vec<A> {
+    type Item = A;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<A, ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        parse_items(text, separator, parse_item)
+    }
+}
+
+impl<'a, A> SeparatorParsable<'a> for VecDeque<A> {
+    type Item = A;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<A, ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        parse_items(text, separator, parse_item)
+    }
+}
+
+impl<'a, A: Eq + Hash> SeparatorParsable<'a> for HashSet<A> {
+    type Item = A;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<A, ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        parse_items(text, separator, parse_item)
+    }
+}
+
+impl<'a, A: Ord> SeparatorParsable<'a> for BTreeSet<A> {
+    type Item = A;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<A, ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        parse_items(text, separator, parse_item)
     }
 }
 
-impl<A: Parsable> SeparatorParsable for Vec<A> {
-    fn parse_separated_by(text: &str, separator: &Regex) -> MyResult<Self> {
-        separator
-            .split(text)
-            .map(A::parse)
-            .collect::<MyResult<Vec<A>>>()
+// The items are `(key, value)` pairs, see `TupleParsable`.
+impl<'a, K: Eq + Hash, V> SeparatorParsable<'a> for HashMap<K, V> {
+    type Item = (K, V);
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<(K, V), ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        parse_items(text, separator, parse_item)
     }
 }
 
+impl<'a, K: Ord, V> SeparatorParsable<'a> for BTreeMap<K, V> {
+    type Item = (K, V);
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<(K, V), ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        parse_items(text, separator, parse_item)
+    }
+}
+
+impl<'a, A, const N: usize> SeparatorParsable<'a> for [A; N] {
+    type Item = A;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<A, ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        let items: Vec<A> = parse_items(text, separator, parse_item)?;
+        let found = items.len();
+        items
+            .try_into()
+            .map_err(|_| ParseError::new(ParseErrorKind::WrongLength { expected: N, found }, text))
+    }
+}
+
+// A tuple whose items are separated by a pattern, splitting at its first matches so that the last
+// item gets the rest. The items of maps are `(key, value)` tuples.
+pub trait TupleParsable<'a>: Sized {
+    fn parse_tuple(text: &'a str, separator: &Pattern) -> Result<Self, ParseError>;
+}
+
+fn parse_tuple_item<'a, A: Parsable<'a>>(text: &'a str, index: &str) -> Result<A, ParseError> {
+    A::parse(text).map_err(|e| e.at(text).with_field(index))
+}
+
+macro_rules! tuple_parsable {
+    ($($index:tt $item:ident),+; $last_index:tt $last_item:ident) => {
+        impl<'a, $($item: Parsable<'a>,)+ $last_item: Parsable<'a>> TupleParsable<'a>
+            for ($($item,)+ $last_item)
+        {
+            fn parse_tuple(text: &'a str, separator: &Pattern) -> Result<Self, ParseError> {
+                let mut buffer = ParseBuffer::new(text);
+                Ok((
+                    $(parse_tuple_item(buffer.read_until(separator)?, stringify!($index))?,)+
+                    parse_tuple_item(buffer.read_to_end(), stringify!($last_index))?,
+                ))
+            }
+        }
+    };
+}
+
+tuple_parsable!(0 A; 1 B);
+tuple_parsable!(0 A, 1 B; 2 C);
+tuple_parsable!(0 A, 1 B, 2 C; 3 D);
+
 pub struct ParseBuffer<'a> {
-    full_input: &'a str,
     remaining_input: &'a str,
 }
 
 impl<'a> ParseBuffer<'a> {
     pub fn new(text: &'a str) -> ParseBuffer<'a> {
         ParseBuffer {
-            full_input: text,
             remaining_input: text,
         }
     }
-    pub fn skip(&mut self, skip_pattern: &Regex) -> MyResult<()> {
-        match skip_pattern.find_at(self.remaining_input, 0) {
+    pub fn skip(&mut self, skip_pattern: &Pattern) -> Result<(), ParseError> {
+        match skip_pattern.find(self.remaining_input) {
             Some(skip_match) => {
-                self.remaining_input = &self.remaining_input[skip_match.end()..];
+                self.remaining_input = &self.remaining_input[skip_match.end..];
                 Ok(())
             }
-            None => {
-                let error_message = format!(
-                    "skip didn't find {:?} when searching in {:?} as part of {:?}",
-                    skip_pattern, self.remaining_input, self.full_input
-                );
-                Err(From::from(error_message))
-            }
+            None => Err(ParseError::new(
+                ParseErrorKind::SkipNotFound {
+                    pattern: skip_pattern.as_str().to_string(),
+                },
+                self.remaining_input,
+            )),
         }
     }
-    pub fn read_until(&mut self, end_pattern: &Regex) -> MyResult<&'a str> {
+    pub fn read_until(&mut self, end_pattern: &Pattern) -> Result<&'a str, ParseError> {
         let remaining_input = self.remaining_input;
         match end_pattern.find(remaining_input) {
             Some(end_match) => {
-                self.remaining_input = &remaining_input[end_match.end()..];
-                Ok(&remaining_input[..end_match.start()])
+                self.remaining_input = &remaining_input[end_match.end..];
+                Ok(&remaining_input[..end_match.start])
             }
+            None => Err(ParseError::new(
+                ParseErrorKind::SeparatorNotFound {
+                    separator: end_pattern.as_str().to_string(),
+                },
+                remaining_input,
+            )),
+        }
+    }
+    // The next `width` chars, for fields without a literal after them.
+    pub fn read_width(&mut self, width: usize) -> Result<&'a str, ParseError> {
+        let remaining_input = self.remaining_input;
+        let end = match remaining_input.char_indices().nth(width) {
+            Some((end, _)) => end,
+            None if remaining_input.chars().count() == width => remaining_input.len(),
             None => {
-                let error_message = format!(
-                    "read_until didn't find {:?} when searching in {:?} as part of {:?}",
-                    end_pattern, self.remaining_input, self.full_input
-                );
-                Err(From::from(error_message))
+                let kind = ParseErrorKind::TooShort {
+                    expected: width,
+                    found: remaining_input.chars().count(),
+                };
+                return Err(ParseError::new(kind, remaining_input));
             }
-        }
+        };
+        self.remaining_input = &remaining_input[end..];
+        Ok(&remaining_input[..end])
+    }
+    pub fn read_to_end(&mut self) -> &'a str {
+        std::mem::take(&mut self.remaining_input)
     }
-    pub fn read_to_end(self) -> &'a str {
+    pub fn remaining(&self) -> &'a str {
         self.remaining_input
     }
 }
@@ -94,28 +388,138 @@ impl<'a> ParseBuffer<'a> {
 mod tests {
 
     use super::*;
+    use crate::MyResult;
 
     #[test]
     fn skip() -> MyResult<()> {
         let mut buffer = ParseBuffer::new("foobar");
-        buffer.skip(&Regex::new("foo").unwrap())?;
+        buffer.skip(&Pattern::new("foo").unwrap())?;
         assert_eq!(buffer.read_to_end(), "bar");
         Ok(())
     }
     #[test]
     fn read_until() -> MyResult<()> {
         let mut buffer = ParseBuffer::new("foobarbaz");
-        assert_eq!(buffer.read_until(&Regex::new("bar").unwrap())?, "foo");
+        assert_eq!(buffer.read_until(&Pattern::new("bar").unwrap())?, "foo");
         assert_eq!(buffer.read_to_end(), "baz");
         Ok(())
     }
     #[test]
+    fn read_width() -> MyResult<()> {
+        let mut buffer = ParseBuffer::new("12é4");
+        assert_eq!(buffer.read_width(3)?, "12é");
+        assert_eq!(
+            buffer.read_width(2).unwrap_err().kind(),
+            &ParseErrorKind::TooShort {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(buffer.read_width(1)?, "4");
+        assert_eq!(Digits::parse("0917")?, Digits(vec![0, 9, 1, 7]));
+        assert_eq!(Digits::parse("09x7").unwrap_err().path(), "[2]");
+        Ok(())
+    }
+    #[test]
     fn parse_separated() -> MyResult<()> {
-        let separator = Regex::new(", ").unwrap();
+        let separator = Pattern::new(", ").unwrap();
         assert_eq!(
             Vec::<i32>::parse_separated_by("1, 2, 3", &separator)?,
             vec![1, 2, 3]
         );
         Ok(())
     }
+    #[test]
+    fn parse_separated_err() {
+        let separator = Pattern::new(", ").unwrap();
+        let input = "1, 2, x";
+        let error = Vec::<i32>::parse_separated_by(input, &separator)
+            .unwrap_err()
+            .locate(input);
+        assert_eq!(error.path(), "[2]");
+        assert_eq!(error.line_and_column(), Some((1, 7)));
+    }
+    #[test]
+    fn parse_collections() -> MyResult<()> {
+        let separator = Pattern::new(",").unwrap();
+        assert_eq!(
+            HashSet::<i32>::parse_separated_by("1,2,1", &separator)?,
+            HashSet::from([1, 2])
+        );
+        assert_eq!(<[i32; 2]>::parse_separated_by("1,2", &separator)?, [1, 2]);
+        let error = <[i32; 2]>::parse_separated_by("1,2,3", &separator).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::WrongLength {
+                expected: 2,
+                found: 3
+            }
+        );
+        let key_value_separator = Pattern::new(": ").unwrap();
+        let map = BTreeMap::<String, i32>::parse_separated_with("a: 1,b: 2", &separator, |item| {
+            TupleParsable::parse_tuple(item, &key_value_separator)
+        })?;
+        assert_eq!(
+            map,
+            BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+        );
+        Ok(())
+    }
+    #[test]
+    fn parse_tuples_options_and_boxes() -> MyResult<()> {
+        let separator = Pattern::new(",").unwrap();
+        assert_eq!(<(i32, i32)>::parse_tuple("3,-4", &separator)?, (3, -4));
+        assert_eq!(
+            <(char, u8, String)>::parse_tuple("a,1,b,c", &separator)?,
+            ('a', 1, "b,c".to_string())
+        );
+        let error = <(i32, i32)>::parse_tuple("3,x", &separator).unwrap_err();
+        assert_eq!(error.path(), ".1");
+        let error = <(i32, i32)>::parse_tuple("3", &separator).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseErrorKind::SeparatorNotFound { .. }
+        ));
+        assert_eq!(Option::<i32>::parse("")?, None);
+        assert_eq!(Option::<i32>::parse("5")?, Some(5));
+        assert!(Option::<i32>::parse("x").is_err());
+        assert_eq!(Box::<i32>::parse("5")?, Box::new(5));
+        Ok(())
+    }
+    #[test]
+    fn parse_foreign_from_str() -> MyResult<()> {
+        let address = FromStrParsable::<std::net::Ipv4Addr>::parse("10.0.0.1")?;
+        assert_eq!(address.0, std::net::Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(address.to_string(), "10.0.0.1");
+        let error = FromStrParsable::<std::net::Ipv4Addr>::parse("10.0.0").unwrap_err();
+        assert!(matches!(error.kind(), ParseErrorKind::FromStr { .. }));
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Even(i32);
+
+    impl TryFrom<i32> for Even {
+        type Error = String;
+        fn try_from(value: i32) -> Result<Even, String> {
+            match value % 2 {
+                0 => Ok(Even(value)),
+                _ => Err(format!("{} is odd", value)),
+            }
+        }
+    }
+
+    try_from_parsable!(Even, i32);
+
+    #[test]
+    fn parse_try_from() -> MyResult<()> {
+        assert_eq!(Even::parse("4")?, Even(4));
+        let text = "3";
+        let error = Even::parse(text).unwrap_err().locate(text);
+        assert_eq!(error.to_string().lines().next(), Some("Even: 3 is odd"));
+        assert_eq!(error.line_and_column(), Some((1, 1)));
+        let error = Even::parse("x").unwrap_err();
+        assert!(matches!(error.kind(), ParseErrorKind::FromStr { .. }));
+        Ok(())
+    }
 }
@@ -0,0 +1,286 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    SkipNotFound {
+        pattern: String,
+    },
+    SeparatorNotFound {
+        separator: String,
+    },
+    FromStr {
+        type_name: &'static str,
+        text: String,
+        message: String,
+    },
+    // One error per variant, in declaration order.
+    NoVariantMatched {
+        errors: Vec<ParseError>,
+    },
+    // More than one variant matched, with `strict_parsing` on.
+    AmbiguousVariants {
+        variants: Vec<&'static str>,
+    },
+    InvalidChar {
+        type_name: &'static str,
+        found: String,
+        valid_chars: &'static [char],
+    },
+    UnevenRows {
+        row: usize,
+        length: usize,
+        expected: usize,
+    },
+    WrongLength {
+        expected: usize,
+        found: usize,
+    },
+    // A `width` field at the end of the text.
+    TooShort {
+        expected: usize,
+        found: usize,
+    },
+    // A field that parsed, but failed a check of `#[validate(...)]`.
+    Invalid {
+        check: &'static str,
+        message: String,
+    },
+    Custom(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::SkipNotFound { pattern } => write!(f, "expected {:?}", pattern),
+            ParseErrorKind::SeparatorNotFound { separator } => {
+                write!(f, "didn't find {:?} after here", separator)
+            }
+            ParseErrorKind::FromStr {
+                type_name,
+                text,
+                message,
+            } => write!(f, "can't parse {:?} as {}: {}", text, type_name, message),
+            ParseErrorKind::NoVariantMatched { errors } => {
+                write!(f, "no variant matched")?;
+                for error in errors {
+                    write!(f, "\n    {}", error.to_string().replace('\n', "\n    "))?;
+                }
+                Ok(())
+            }
+            ParseErrorKind::AmbiguousVariants { variants } => {
+                write!(f, "more than one variant matched: {}", variants.join(", "))
+            }
+            ParseErrorKind::InvalidChar {
+                type_name,
+                found,
+                valid_chars,
+            } => {
+                let valid_chars = valid_chars
+                    .iter()
+                    .map(|c| format!("{:?}", c))
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "{:?} is not a valid {}, expected one of {}",
+                    found,
+                    type_name,
+                    valid_chars.join(", ")
+                )
+            }
+            ParseErrorKind::UnevenRows {
+                row,
+                length,
+                expected,
+            } => write!(
+                f,
+                "row {} has {} cells, but row 0 has {}",
+                row, length, expected
+            ),
+            ParseErrorKind::WrongLength { expected, found } => {
+                write!(f, "expected {} items, found {}", expected, found)
+            }
+            ParseErrorKind::TooShort { expected, found } => {
+                write!(f, "expected {} chars, found {}", expected, found)
+            }
+            ParseErrorKind::Invalid { check, message } => {
+                write!(f, "failed `{}`: {}", check, message)
+            }
+            ParseErrorKind::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    line: usize,
+    column: usize,
+    line_text: String,
+}
+
+// The errors only remember the address of the text they happened at, as every parsed `&str` is a
+// slice of the original input. `locate` turns it into a line and column once the input is known.
+// Boxed, so that results returning it stay small.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError(Box<ParseErrorData>);
+
+#[derive(Clone, PartialEq, Eq)]
+struct ParseErrorData {
+    kind: ParseErrorKind,
+    root: Option<&'static str>,
+    // Innermost first.
+    path: Vec<String>,
+    address: Option<usize>,
+    location: Option<Location>,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, at: &str) -> ParseError {
+        ParseError::from(kind).at(at)
+    }
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.0.kind
+    }
+    // Keeps the address if there already is one, as it is more precise.
+    pub fn at(mut self, text: &str) -> ParseError {
+        self.0.address.get_or_insert(text.as_ptr() as usize);
+        self
+    }
+    // Each enclosing type overwrites it, so the outermost one is shown.
+    pub fn with_root(mut self, type_name: &'static str) -> ParseError {
+        self.0.root = Some(type_name);
+        self
+    }
+    pub fn with_field(mut self, field_name: &str) -> ParseError {
+        self.0.path.push(format!(".{}", field_name));
+        self
+    }
+    pub fn with_index(mut self, index: usize) -> ParseError {
+        self.0.path.push(format!("[{}]", index));
+        self
+    }
+    pub fn path(&self) -> String {
+        let mut path = self.0.root.unwrap_or_default().to_string();
+        path.extend(self.0.path.iter().rev().map(String::as_str));
+        path
+    }
+    pub fn line_and_column(&self) -> Option<(usize, usize)> {
+        self.0
+            .location
+            .as_ref()
+            .map(|location| (location.line, location.column))
+    }
+    // `input` must be the text the parsing started from.
+    pub fn locate(mut self, input: &str) -> ParseError {
+        if let ParseErrorKind::NoVariantMatched { errors } = &mut self.0.kind {
+            *errors = errors.drain(..).map(|error| error.locate(input)).collect();
+        }
+        let start = input.as_ptr() as usize;
+        let Some(offset) = self
+            .0
+            .address
+            .and_then(|address| address.checked_sub(start))
+            .filter(|&offset| offset <= input.len() && input.is_char_boundary(offset))
+        else {
+            return self;
+        };
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        self.0.location = Some(Location {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            line_text: input[line_start..line_end].to_string(),
+        });
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path();
+        if !path.is_empty() {
+            write!(f, "{}: ", path)?;
+        }
+        write!(f, "{}", self.0.kind)?;
+        if let Some(Location {
+            line,
+            column,
+            line_text,
+        }) = &self.0.location
+        {
+            let gutter = " ".repeat(line.to_string().len());
+            write!(f, "\n{}--> line {}, column {}", gutter, line, column)?;
+            write!(f, "\n{} | {}", line, line_text)?;
+            write!(f, "\n{} | {}^", gutter, " ".repeat(column - 1))?;
+        }
+        Ok(())
+    }
+}
+
+// `main` prints the `Debug` of the error, where the caret should still line up.
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseErrorKind> for ParseError {
+    fn from(kind: ParseErrorKind) -> ParseError {
+        ParseError(Box::new(ParseErrorData {
+            kind,
+            root: None,
+            path: Vec::new(),
+            address: None,
+            location: None,
+        }))
+    }
+}
+
+// For hand-written `Parsable` implementations that fail on something other than the text.
+impl From<Box<dyn Error>> for ParseError {
+    fn from(error: Box<dyn Error>) -> ParseError {
+        match error.downcast::<ParseError>() {
+            Ok(error) => *error,
+            Err(error) => ParseErrorKind::Custom(error.to_string()).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_and_location() {
+        let input = "a: 1\nb: 2x\n";
+        let kind = ParseErrorKind::Custom("bad number".to_string());
+        let error = ParseError::new(kind, &input[8..])
+            .with_root("Inner")
+            .with_field("value")
+            .with_index(1)
+            .with_root("Outer")
+            .with_field("items")
+            .with_root("InputFormat")
+            .locate(input);
+        assert_eq!(error.path(), "InputFormat.items[1].value");
+        assert_eq!(error.line_and_column(), Some((2, 4)));
+        assert_eq!(
+            error.to_string(),
+            "InputFormat.items[1].value: bad number\n --> line 2, column 4\n2 | b: 2x\n  |    ^"
+        );
+    }
+    #[test]
+    fn locate_elsewhere() {
+        let other_text = String::from("other text");
+        let kind = ParseErrorKind::Custom("bad".to_string());
+        let error = ParseError::new(kind, &other_text).locate("input");
+        assert_eq!(error.line_and_column(), None);
+        assert_eq!(error.to_string(), "bad");
+    }
+}
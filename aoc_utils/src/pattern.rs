@@ -0,0 +1,186 @@
+use std::{borrow::Cow, ops::Range};
+
+use regex::Regex;
+use regex_syntax::hir::HirKind;
+
+// A separator or a literal between fields. Most of them are plain text like `"\n"` or `", "`, which
+// `str::find` and `str::split` handle much faster than a regex.
+#[derive(Debug, Clone)]
+pub struct Pattern(PatternKind);
+
+// Only built by the constructors of `Pattern`, which never make an empty `Literal`.
+#[derive(Debug, Clone)]
+enum PatternKind {
+    Literal(Cow<'static, str>),
+    Regex(Regex),
+}
+
+impl Pattern {
+    // A literal if the regex only matches one non-empty text, like `"X\\+"`.
+    pub fn new(pattern: &str) -> Result<Pattern, regex::Error> {
+        let regex = Regex::new(pattern)?;
+        let literal = match regex_syntax::parse(pattern).map(|hir| hir.into_kind()) {
+            Ok(HirKind::Literal(literal)) => String::from_utf8(literal.0.into_vec()).ok(),
+            _ => None,
+        };
+        Ok(Pattern(match literal {
+            Some(literal) if !literal.is_empty() => PatternKind::Literal(Cow::Owned(literal)),
+            _ => PatternKind::Regex(regex),
+        }))
+    }
+    // What `make_pattern!` builds at compile time, `None` unless `is_plain_literal(pattern)`.
+    pub const fn plain_literal(pattern: &'static str) -> Option<Pattern> {
+        if is_plain_literal(pattern) {
+            Some(Pattern(PatternKind::Literal(Cow::Borrowed(pattern))))
+        } else {
+            None
+        }
+    }
+    // The literal, or the regex it was made from.
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            PatternKind::Literal(literal) => literal,
+            PatternKind::Regex(regex) => regex.as_str(),
+        }
+    }
+    // The range of the first match in `text`.
+    pub fn find(&self, text: &str) -> Option<Range<usize>> {
+        match &self.0 {
+            PatternKind::Literal(literal) => {
+                find_literal(text, literal).map(|start| start..start + literal.len())
+            }
+            PatternKind::Regex(regex) => regex.find(text).map(|found| found.range()),
+        }
+    }
+    pub fn split<'p, 't>(&'p self, text: &'t str) -> Split<'p, 't> {
+        match &self.0 {
+            PatternKind::Literal(literal) => match single_char(literal) {
+                Some(c) => Split::Char(text.split(c)),
+                None => Split::Literal(text.split(literal.as_ref())),
+            },
+            PatternKind::Regex(regex) => Split::Regex(regex.split(text)),
+        }
+    }
+}
+
+// Always searched with the regex, even when it's a plain literal.
+impl From<Regex> for Pattern {
+    fn from(regex: Regex) -> Pattern {
+        Pattern(PatternKind::Regex(regex))
+    }
+}
+
+pub enum Split<'p, 't> {
+    Char(std::str::Split<'t, char>),
+    Literal(std::str::Split<'t, &'p str>),
+    Regex(regex::Split<'p, 't>),
+}
+
+impl<'t> Iterator for Split<'_, 't> {
+    type Item = &'t str;
+    fn next(&mut self) -> Option<&'t str> {
+        match self {
+            Split::Char(split) => split.next(),
+            Split::Literal(split) => split.next(),
+            Split::Regex(split) => split.next(),
+        }
+    }
+}
+
+fn single_char(literal: &str) -> Option<char> {
+    let mut chars = literal.chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
+
+// On the short texts between fields, `str::find` with a `&str` spends more time setting up its
+// search than searching, so this looks for the first byte with `memchr` instead. `literal` is
+// never empty.
+fn find_literal(text: &str, literal: &str) -> Option<usize> {
+    let (text, literal) = (text.as_bytes(), literal.as_bytes());
+    let mut from = 0;
+    while let Some(offset) = memchr::memchr(literal[0], &text[from..]) {
+        let start = from + offset;
+        if text[start..].starts_with(literal) {
+            return Some(start);
+        }
+        from = start + 1;
+    }
+    None
+}
+
+// Whether `make_pattern!` can skip the regex for `pattern`, checked at compile time. Only the
+// metacharacters of `regex_syntax::is_meta_character` can make a regex match something else.
+const fn is_plain_literal(pattern: &str) -> bool {
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if matches!(
+            bytes[i],
+            b'\\'
+                | b'.'
+                | b'+'
+                | b'*'
+                | b'?'
+                | b'('
+                | b')'
+                | b'|'
+                | b'['
+                | b']'
+                | b'{'
+                | b'}'
+                | b'^'
+                | b'$'
+                | b'#'
+                | b'&'
+                | b'-'
+                | b'~'
+        ) {
+            return false;
+        }
+        i += 1;
+    }
+    !bytes.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals() {
+        assert!(is_plain_literal(", "));
+        assert!(is_plain_literal("\n"));
+        assert!(!is_plain_literal(" +"));
+        assert!(!is_plain_literal(""));
+        assert!(matches!(
+            Pattern::new("X\\+").unwrap().0,
+            PatternKind::Literal(literal) if literal == "X+"
+        ));
+        assert!(matches!(
+            Pattern::new(", *").unwrap().0,
+            PatternKind::Regex(_)
+        ));
+        assert!(matches!(Pattern::new("").unwrap().0, PatternKind::Regex(_)));
+        assert!(Pattern::plain_literal("").is_none());
+        assert_eq!(Pattern::new("").unwrap().find("ab"), Some(0..0));
+    }
+    #[test]
+    fn literal_and_regex_agree() {
+        let text = "a, b,, c,";
+        let literal = Pattern::new(",").unwrap();
+        let regex = Pattern::from(Regex::new(",").unwrap());
+        assert_eq!(
+            literal.split(text).collect::<Vec<_>>(),
+            regex.split(text).collect::<Vec<_>>()
+        );
+        assert_eq!(literal.find(text), Some(1..2));
+        assert_eq!(regex.find(text), Some(1..2));
+        assert_eq!(literal.find("abc"), None);
+        let literal = Pattern::new(" v=").unwrap();
+        assert_eq!(literal.find("p=1 v v=2"), Some(5..8));
+        assert_eq!(
+            literal.split("1 v=2 v=").collect::<Vec<_>>(),
+            ["1", "2", ""]
+        );
+    }
+}
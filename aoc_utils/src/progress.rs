@@ -0,0 +1,171 @@
+use std::{
+    io::{stderr, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use crate::cancellation::{cancellation_token, CancellationToken, Cancelled};
+
+const RENDER_INTERVAL: Duration = Duration::from_millis(100);
+
+struct ProgressState {
+    total: usize,
+    done: AtomicUsize,
+    start_time: Instant,
+    token: CancellationToken,
+}
+
+static CURRENT_PROGRESS: Mutex<Option<Arc<ProgressState>>> = Mutex::new(None);
+
+// Clones share the same counter, so it can be moved into scoped threads or rayon closures.
+// The runner renders the most recently created one. `inc` fails once the solve is cancelled.
+#[derive(Clone)]
+pub struct Progress {
+    state: Arc<ProgressState>,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Progress {
+        let state = Arc::new(ProgressState {
+            total,
+            done: AtomicUsize::new(0),
+            start_time: Instant::now(),
+            token: cancellation_token(),
+        });
+        *CURRENT_PROGRESS.lock().unwrap() = Some(state.clone());
+        Progress { state }
+    }
+    pub fn inc(&self) -> Result<(), Cancelled> {
+        self.inc_by(1)
+    }
+    pub fn inc_by(&self, amount: usize) -> Result<(), Cancelled> {
+        self.state.done.fetch_add(amount, Ordering::Relaxed);
+        self.state.token.check()
+    }
+    pub fn done(&self) -> usize {
+        self.state.done.load(Ordering::Relaxed)
+    }
+    pub fn total(&self) -> usize {
+        self.state.total
+    }
+}
+
+impl ProgressState {
+    fn render(&self, label: &str) -> String {
+        let done = self.done.load(Ordering::Relaxed).min(self.total);
+        let fraction = if self.total == 0 {
+            1.0
+        } else {
+            done as f64 / self.total as f64
+        };
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        let eta = if done == 0 {
+            "?".to_string()
+        } else {
+            format!("{:.1}s", elapsed / fraction - elapsed)
+        };
+        format!(
+            "{}: {}/{} ({:.1}%) ETA {}",
+            label,
+            done,
+            self.total,
+            fraction * 100.0,
+            eta
+        )
+    }
+}
+
+fn clear_line(output: &mut impl Write) {
+    let _ = write!(output, "\r\x1b[2K");
+    let _ = output.flush();
+}
+
+pub(crate) fn with_progress_rendering<R>(label: &str, computation: impl FnOnce() -> R) -> R {
+    *CURRENT_PROGRESS.lock().unwrap() = None;
+    if !stderr().is_terminal() {
+        return computation();
+    }
+    let result = render_while(label, computation);
+    *CURRENT_PROGRESS.lock().unwrap() = None;
+    result
+}
+
+fn render_while<R>(label: &str, computation: impl FnOnce() -> R) -> R {
+    let finished = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let renderer = scope.spawn(|| {
+            let mut rendered_anything = false;
+            loop {
+                thread::park_timeout(RENDER_INTERVAL);
+                if finished.load(Ordering::Relaxed) {
+                    break;
+                }
+                let current = CURRENT_PROGRESS.lock().unwrap().clone();
+                if let Some(state) = current {
+                    let mut output = stderr().lock();
+                    clear_line(&mut output);
+                    let _ = write!(output, "{}", state.render(label));
+                    let _ = output.flush();
+                    rendered_anything = true;
+                }
+            }
+            if rendered_anything {
+                clear_line(&mut stderr().lock());
+            }
+        });
+        // The scope waits for the renderer even when the computation panics.
+        let _stop = StopRenderer {
+            finished: &finished,
+            renderer: renderer.thread(),
+        };
+        computation()
+    })
+}
+
+struct StopRenderer<'a> {
+    finished: &'a AtomicBool,
+    renderer: &'a Thread,
+}
+
+impl Drop for StopRenderer<'_> {
+    fn drop(&mut self) {
+        self.finished.store(true, Ordering::Relaxed);
+        self.renderer.unpark();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_between_threads() {
+        let progress = Progress::new(100);
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                let progress = progress.clone();
+                scope.spawn(move || {
+                    for _ in 0..25 {
+                        progress.inc().unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(progress.done(), 100);
+        assert_eq!(progress.total(), 100);
+    }
+    #[test]
+    fn panicking_computation_stops_rendering() {
+        let result = std::panic::catch_unwind(|| {
+            render_while("Part 1", || {
+                Progress::new(10).inc().unwrap();
+                panic!("solve failed");
+            })
+        });
+        assert!(result.is_err());
+    }
+}
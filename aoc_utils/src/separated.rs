@@ -0,0 +1,168 @@
+use std::{
+    fmt::{Debug, Display, Formatter},
+    iter::Enumerate,
+    rc::Rc,
+};
+
+use crate::{Parsable, ParseError, Pattern, SeparatorParsable, Split, WriteSeparated};
+
+// Items that are only parsed while iterating over them, for inputs too big to hold parsed. As a
+// `separated_by` field it keeps the separator and the item parser, and on its own it splits lines.
+// Every iteration parses the items again, and each of them can fail with its own error.
+pub struct Separated<'a, T> {
+    text: &'a str,
+    separator: Pattern,
+    parse_item: Rc<dyn Fn(&'a str) -> Result<T, ParseError> + 'a>,
+}
+
+impl<'a, T> Separated<'a, T> {
+    pub fn iter(&self) -> SeparatedIter<'_, 'a, T> {
+        SeparatedIter {
+            items: self.separator.split(self.text).enumerate(),
+            parse_item: self.parse_item.as_ref(),
+        }
+    }
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+}
+
+impl<T> Clone for Separated<'_, T> {
+    fn clone(&self) -> Self {
+        Separated {
+            text: self.text,
+            separator: self.separator.clone(),
+            parse_item: self.parse_item.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Separated<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Separated")
+            .field("separator", &self.separator.as_str())
+            .field("length", &self.text.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> SeparatorParsable<'a> for Separated<'a, T> {
+    type Item = T;
+    fn parse_separated_with(
+        text: &'a str,
+        separator: &Pattern,
+        parse_item: impl Fn(&'a str) -> Result<T, ParseError> + 'a,
+    ) -> Result<Self, ParseError> {
+        Ok(Separated {
+            text,
+            separator: separator.clone(),
+            parse_item: Rc::new(parse_item),
+        })
+    }
+}
+
+impl<'a, T: Parsable<'a>> Parsable<'a> for Separated<'a, T> {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        Self::parse_separated_by(text, crate::make_pattern!("\n"))
+    }
+}
+
+pub struct SeparatedIter<'s, 'a, T> {
+    items: Enumerate<Split<'s, 'a>>,
+    parse_item: &'s dyn Fn(&'a str) -> Result<T, ParseError>,
+}
+
+impl<T> Iterator for SeparatedIter<'_, '_, T> {
+    type Item = Result<T, ParseError>;
+    fn next(&mut self) -> Option<Result<T, ParseError>> {
+        let (i, item) = self.items.next()?;
+        Some((self.parse_item)(item).map_err(|e| e.at(item).with_index(i)))
+    }
+}
+
+// Written as the text it was read from, which round trips even when some items don't parse.
+impl<T> Display for Separated<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+impl<'s, T> WriteSeparated for &'s Separated<'_, T> {
+    type Item = &'s T;
+    fn write_separated(
+        self,
+        f: &mut Formatter<'_>,
+        _separator: &str,
+        _write_item: impl FnMut(&mut Formatter<'_>, &'s T) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formatted_struct, MyResult, ParseErrorKind};
+
+    formatted_struct! {
+        #[display]
+        #[derive(Debug)]
+        struct TestSeparated<'a> {
+            "rows:",
+            #[separated_by("\n", ",")]
+            rows: Separated<'a, Vec<i32>>,
+        }
+    }
+
+    #[derive(crate::Parsable, Debug)]
+    struct DerivedSeparated<'a> {
+        #[parse(sep = " ")]
+        words: Separated<'a, &'a str>,
+    }
+
+    #[test]
+    fn parse_while_iterating() -> MyResult<()> {
+        let text = "1\n2\nx\n4";
+        let lines = Separated::<i32>::parse(text)?;
+        let mut items = lines.iter();
+        assert_eq!(items.next().transpose()?, Some(1));
+        assert_eq!(items.next().transpose()?, Some(2));
+        let error = items.next().unwrap().unwrap_err().locate(text);
+        assert_eq!(error.path(), "[2]");
+        assert_eq!(error.line_and_column(), Some((3, 1)));
+        assert!(matches!(error.kind(), ParseErrorKind::FromStr { .. }));
+        assert_eq!(items.next().transpose()?, Some(4));
+        assert_eq!(items.next().transpose()?, None);
+        Ok(())
+    }
+    #[test]
+    fn nested() -> MyResult<()> {
+        let rows = Separated::<Vec<i32>>::parse_separated_with(
+            "1,2;3",
+            &Pattern::new(";").unwrap(),
+            |row| Vec::parse_separated_by(row, crate::make_pattern!(",")),
+        )?;
+        assert_eq!(
+            rows.iter().collect::<Result<Vec<_>, _>>()?,
+            vec![vec![1, 2], vec![3]]
+        );
+        assert_eq!(rows.iter().count(), 2);
+        Ok(())
+    }
+    #[test]
+    fn parse_fields() -> MyResult<()> {
+        // The bad row only fails once it's reached, and is written back as it was.
+        let text = "rows:1,2\n3,x\n4";
+        let parsed = TestSeparated::parse(text)?;
+        let error = parsed.rows.iter().nth(1).unwrap().unwrap_err();
+        assert_eq!(error.path(), "[1][1]");
+        assert_eq!(parsed.rows.iter().filter(Result::is_ok).count(), 2);
+        assert_eq!(parsed.to_string(), text);
+        let words = DerivedSeparated::parse("a bc d")?.words;
+        assert_eq!(
+            words.iter().collect::<Result<Vec<_>, _>>()?,
+            ["a", "bc", "d"]
+        );
+        Ok(())
+    }
+}
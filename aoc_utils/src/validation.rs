@@ -0,0 +1,135 @@
+use std::{error::Error, fmt::Debug, ops::RangeBounds};
+
+use crate::{ParseError, ParseErrorKind};
+
+// The checks of `#[validate(...)]`, run on a field right after it's parsed. Each one returns
+// `Result<(), E>`, like the functions of `with = ...`, and `validated` names the check that failed.
+#[macro_export]
+macro_rules! validate_field {
+    ($value:ident $(,)?) => {
+        Ok::<(), $crate::ParseError>(())
+    };
+    ($value:ident, range = $range:expr $(, $($rest:tt)*)?) => {
+        $crate::validated(
+            $crate::check_range($value, $range),
+            stringify!(range = $range),
+        )
+        .and_then(|()| $crate::validate_field!($value, $($($rest)*)?))
+    };
+    ($value:ident, non_empty $(, $($rest:tt)*)?) => {
+        $crate::validated(
+            if $value.is_empty() { Err("it is empty") } else { Ok(()) },
+            "non_empty",
+        )
+        .and_then(|()| $crate::validate_field!($value, $($($rest)*)?))
+    };
+    // Called directly rather than passed along, so that a `fn(&[T])` can check a `Vec<T>`.
+    ($value:ident, with = $function:path $(, $($rest:tt)*)?) => {
+        $crate::validated($function($value), stringify!(with = $function))
+            .and_then(|()| $crate::validate_field!($value, $($($rest)*)?))
+    };
+}
+
+pub fn check_range<T: PartialOrd + Debug>(
+    value: &T,
+    range: impl RangeBounds<T>,
+) -> Result<(), String> {
+    if range.contains(value) {
+        Ok(())
+    } else {
+        Err(format!("{:?} is out of range", value))
+    }
+}
+
+pub fn validated<E: Into<Box<dyn Error>>>(
+    result: Result<(), E>,
+    check: &'static str,
+) -> Result<(), ParseError> {
+    result.map_err(|e| {
+        ParseError::from(ParseErrorKind::Invalid {
+            check,
+            message: e.into().to_string(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formatted_struct, MyResult, Parsable};
+
+    fn is_odd(value: &i32) -> Result<(), String> {
+        match value % 2 {
+            0 => Err(format!("{} is even", value)),
+            _ => Ok(()),
+        }
+    }
+
+    fn no_sevens(values: &[i32]) -> Result<(), &'static str> {
+        match values.contains(&7) {
+            true => Err("found a 7"),
+            false => Ok(()),
+        }
+    }
+
+    formatted_struct! {
+        #[derive(Debug)]
+        struct TestValidated {
+            #[validate(range = 0..8, with = is_odd)]
+            small: i32,
+            ":",
+            #[separated_by = ","]
+            #[validate(non_empty, with = no_sevens)]
+            values: Vec<i32>,
+        }
+    }
+
+    #[derive(crate::Parsable, Debug)]
+    #[parse(format = "{name}={size}")]
+    struct DerivedValidated<'a> {
+        #[validate(non_empty)]
+        name: &'a str,
+        #[validate(range = 1..=9, with = is_odd)]
+        size: i32,
+    }
+
+    #[test]
+    fn validate_fields() -> MyResult<()> {
+        let parsed = TestValidated::parse("3:1,2")?;
+        assert_eq!((parsed.small, parsed.values), (3, vec![1, 2]));
+        let text = "9:1";
+        let error = TestValidated::parse(text).unwrap_err().locate(text);
+        assert_eq!(error.path(), "TestValidated.small");
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::Invalid {
+                check: "range = 0..8",
+                message: "9 is out of range".to_string()
+            }
+        );
+        assert_eq!(error.line_and_column(), Some((1, 1)));
+        let error = TestValidated::parse("2:1").unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseErrorKind::Invalid {
+                check: "with = is_odd",
+                ..
+            }
+        ));
+        let text = "1:1,7";
+        let error = TestValidated::parse(text).unwrap_err().locate(text);
+        assert_eq!(error.path(), "TestValidated.values");
+        assert_eq!(error.line_and_column(), Some((1, 3)));
+        assert!(TestValidated::parse("1:").is_err());
+        let parsed = DerivedValidated::parse("a=3")?;
+        assert_eq!((parsed.name, parsed.size), ("a", 3));
+        let error = DerivedValidated::parse("=3").unwrap_err();
+        assert_eq!(error.path(), "DerivedValidated.name");
+        assert_eq!(
+            error.to_string(),
+            "DerivedValidated.name: failed `non_empty`: it is empty"
+        );
+        assert!(DerivedValidated::parse("a=11").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{ParseError, ParseErrorKind};
+
+// A variant's name and parser, which also returns the length of the text it left unread.
+pub type VariantParser<'p, 'a, T> = (
+    &'static str,
+    &'p dyn Fn(&'a str) -> Result<(T, usize), ParseError>,
+);
+
+// The enums of `formatted_struct!` and `#[derive(Parsable)]` take the first variant that parses
+// the text, or with `longest_match` the one that leaves the least of it unread. With the
+// `strict_parsing` feature every variant is tried, and a tie between them is an error.
+pub fn parse_variants<'a, T>(
+    text: &'a str,
+    longest_match: bool,
+    variants: &[VariantParser<'_, 'a, T>],
+) -> Result<T, ParseError> {
+    choose_variant(
+        text,
+        longest_match,
+        cfg!(feature = "strict_parsing"),
+        variants,
+    )
+}
+
+fn choose_variant<'a, T>(
+    text: &'a str,
+    longest_match: bool,
+    strict: bool,
+    variants: &[VariantParser<'_, 'a, T>],
+) -> Result<T, ParseError> {
+    let mut errors = Vec::new();
+    let mut matched = Vec::new();
+    for &(name, parse) in variants {
+        match parse(text) {
+            Ok((value, _)) if !longest_match && !strict => return Ok(value),
+            Ok((value, unread)) => matched.push((name, value, unread)),
+            Err(e) => errors.push(e.with_root(name)),
+        }
+    }
+    if longest_match {
+        let least_unread = matched.iter().map(|&(_, _, unread)| unread).min();
+        matched.retain(|&(_, _, unread)| Some(unread) == least_unread);
+    }
+    if strict && matched.len() > 1 {
+        let variants = matched.iter().map(|&(name, _, _)| name).collect();
+        return Err(ParseError::new(
+            ParseErrorKind::AmbiguousVariants { variants },
+            text,
+        ));
+    }
+    match matched.into_iter().next() {
+        Some((_, value, _)) => Ok(value),
+        None => Err(ParseError::new(
+            ParseErrorKind::NoVariantMatched { errors },
+            text,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MyResult, Parsable};
+
+    // `Number` reads the whole text, `Prefix` only what's before the first `-`.
+    fn parse_number(text: &str) -> Result<(i32, usize), ParseError> {
+        Ok((i32::parse(text)?, 0))
+    }
+    fn parse_prefix(text: &str) -> Result<(i32, usize), ParseError> {
+        let (prefix, rest) = text
+            .split_once('-')
+            .ok_or(ParseErrorKind::Custom("no `-`".to_string()))?;
+        Ok((-(prefix.len() as i32), rest.len()))
+    }
+    fn choose(text: &str, longest_match: bool, strict: bool) -> Result<i32, ParseError> {
+        choose_variant(
+            text,
+            longest_match,
+            strict,
+            &[("Prefix", &parse_prefix), ("Number", &parse_number)],
+        )
+    }
+
+    #[test]
+    fn first_match() -> MyResult<()> {
+        assert_eq!(choose("ab-1", false, false)?, -2);
+        assert_eq!(choose("-1", false, false)?, 0);
+        assert_eq!(choose("12", false, false)?, 12);
+        let error = choose("ab", false, false).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseErrorKind::NoVariantMatched { errors } if errors.len() == 2
+        ));
+        Ok(())
+    }
+    #[test]
+    fn longest_match() -> MyResult<()> {
+        assert_eq!(choose("-1", true, false)?, -1);
+        assert_eq!(choose("ab-1", true, false)?, -2);
+        Ok(())
+    }
+    #[test]
+    fn strict() -> MyResult<()> {
+        let error = choose("-1", false, true).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::AmbiguousVariants {
+                variants: vec!["Prefix", "Number"]
+            }
+        );
+        // `Number` leaves less unread, so the match isn't ambiguous.
+        assert_eq!(choose("-1", true, true)?, -1);
+        assert_eq!(choose("12", false, true)?, 12);
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+// `{name}` (or `{0}` for tuple fields) is a field, `{{` and `}}` are literal braces, and everything
+// else is a regex, like the literals of `formatted_struct!`.
+pub(crate) fn parse_format(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed `{{{}`", name)),
+                    }
+                }
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(format!("`{{{}}}` is not a field name", name));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal one".to_string()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    if segments.is_empty() {
+        return Err("the format is empty".to_string());
+    }
+    for segment in &segments {
+        if let Segment::Literal(literal) = segment {
+            check_regex(literal)?;
+        }
+    }
+    Ok(segments)
+}
+
+pub(crate) fn check_regex(pattern: &str) -> Result<(), String> {
+    Regex::new(pattern)
+        .map(|_| ())
+        .map_err(|e| format!("{:?} is not a valid regex: {}", pattern, e))
+}
+
+// Each field is read from the group of its name, or from group `i + 1` for the tuple field `i`.
+pub(crate) fn check_captures(pattern: &str, fields: &[&str]) -> Result<(), String> {
+    let regex =
+        Regex::new(pattern).map_err(|e| format!("{:?} is not a valid regex: {}", pattern, e))?;
+    for field in fields {
+        let has_group = match field.parse::<usize>() {
+            Ok(i) => i + 1 < regex.captures_len(),
+            Err(_) => regex.capture_names().any(|name| name == Some(field)),
+        };
+        if !has_group {
+            return Err(match field.parse::<usize>() {
+                Ok(i) => format!("the regex has no group {} for field `{}`", i + 1, field),
+                Err(_) => format!("the regex has no group `(?<{}>...)`", field),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_and_literals() {
+        assert_eq!(
+            parse_format("p={p_x},{p_y} v=\\{{{0}\\}}"),
+            Ok(vec![
+                Segment::Literal("p=".to_string()),
+                Segment::Field("p_x".to_string()),
+                Segment::Literal(",".to_string()),
+                Segment::Field("p_y".to_string()),
+                Segment::Literal(" v=\\{".to_string()),
+                Segment::Field("0".to_string()),
+                Segment::Literal("\\}".to_string()),
+            ])
+        );
+    }
+    #[test]
+    fn adjacent_fields() {
+        assert_eq!(
+            parse_format("{a}{b}"),
+            Ok(vec![
+                Segment::Field("a".to_string()),
+                Segment::Field("b".to_string()),
+            ])
+        );
+    }
+    #[test]
+    fn invalid_formats() {
+        assert!(parse_format("{a").is_err());
+        assert!(parse_format("a}").is_err());
+        assert!(parse_format("{a b}").is_err());
+        assert!(parse_format("").is_err());
+        assert!(parse_format("({a}").is_err());
+    }
+    #[test]
+    fn capture_groups() {
+        assert_eq!(
+            check_captures(r"mul\((?<lh>\d+),(?<rh>\d+)\)", &["lh", "rh"]),
+            Ok(())
+        );
+        assert_eq!(check_captures(r"(\d+)-(\d+)", &["0", "1"]), Ok(()));
+        assert!(check_captures(r"(?<lh>\d+)", &["lh", "rh"]).is_err());
+        assert!(check_captures(r"(\d+)", &["0", "1"]).is_err());
+        assert!(check_captures(r"(\d+", &[]).is_err());
+    }
+}
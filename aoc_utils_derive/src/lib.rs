@@ -0,0 +1,579 @@
+mod format;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parenthesized, parse_macro_input, parse_quote, punctuated::Punctuated,
+    Attribute, Data, DeriveInput, Expr, Fields, LitInt, LitStr, Member, Path, Token, Type,
+    WherePredicate,
+};
+
+use crate::format::{check_captures, check_regex, parse_format, Segment};
+
+// The attribute-style counterpart of `formatted_struct!`, see the README for the attributes.
+#[proc_macro_derive(Parsable, attributes(parse, validate))]
+pub fn derive_parsable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Pattern {
+    Format(LitStr),
+    Captures(LitStr),
+}
+
+// `#[parse(longest_match)]`, the only attribute of an enum itself.
+fn parse_longest_match(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut longest_match = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") || meta.path.is_ident("captures") {
+                return Err(meta.error("an enum's patterns go on its variants"));
+            }
+            if !meta.path.is_ident("longest_match") {
+                return Err(meta.error("expected `longest_match` here"));
+            }
+            longest_match = true;
+            Ok(())
+        })?;
+    }
+    Ok(longest_match)
+}
+
+// `#[parse(format = "...")]` or `#[parse(captures = "...")]`, erroring on any other key.
+fn parse_pattern(attrs: &[Attribute]) -> syn::Result<Option<Pattern>> {
+    let mut pattern = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            let make_pattern = if meta.path.is_ident("format") {
+                Pattern::Format
+            } else if meta.path.is_ident("captures") {
+                Pattern::Captures
+            } else {
+                return Err(meta.error("expected `format` or `captures` here"));
+            };
+            if pattern.is_some() {
+                return Err(meta.error("only one of `format` and `captures` can be given"));
+            }
+            pattern = Some(make_pattern(meta.value()?.parse()?));
+            Ok(())
+        })?;
+    }
+    Ok(pattern)
+}
+
+struct FieldAttributes {
+    separators: Vec<LitStr>,
+    tuple_separator: Option<LitStr>,
+    width: Option<LitInt>,
+    // The checks of `#[validate(...)]`, passed on to `validate_field!`.
+    validations: Vec<TokenStream2>,
+}
+
+// `#[parse(sep = "...")]`, or `#[parse(sep("\n\n", "\n"))]` for nested collections,
+// `#[parse(tuple_sep = "...")]` for tuples, which is called `key_value_sep` for the items of maps,
+// and `#[parse(width = N)]` for fields of `N` chars.
+fn parse_field_attributes(attrs: &[Attribute]) -> syn::Result<FieldAttributes> {
+    let mut separators = Vec::new();
+    let mut tuple_separator = None::<LitStr>;
+    let mut width = None::<LitInt>;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("width") {
+                if width.is_some() {
+                    return Err(meta.error("`width` is given more than once"));
+                }
+                let value = meta.value()?.parse::<LitInt>()?;
+                if value.base10_parse::<usize>()? == 0 {
+                    return Err(syn::Error::new(value.span(), "`width` has to be positive"));
+                }
+                width = Some(value);
+                return Ok(());
+            }
+            if meta.path.is_ident("tuple_sep") || meta.path.is_ident("key_value_sep") {
+                if tuple_separator.is_some() {
+                    return Err(meta.error("the tuple separator is given more than once"));
+                }
+                tuple_separator = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            if !meta.path.is_ident("sep") {
+                return Err(
+                    meta.error("expected `sep`, `tuple_sep`, `key_value_sep` or `width` here")
+                );
+            }
+            if !separators.is_empty() {
+                return Err(meta.error("`sep` is given more than once"));
+            }
+            if meta.input.peek(Token![=]) {
+                separators.push(meta.value()?.parse()?);
+            } else {
+                let content;
+                parenthesized!(content in meta.input);
+                separators.extend(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?);
+            }
+            Ok(())
+        })?;
+    }
+    for separator in separators.iter().chain(&tuple_separator) {
+        check_regex(&separator.value()).map_err(|e| syn::Error::new(separator.span(), e))?;
+    }
+    Ok(FieldAttributes {
+        separators,
+        tuple_separator,
+        width,
+        validations: parse_validations(attrs)?,
+    })
+}
+
+// `#[validate(range = 0..8, non_empty, with = path::to::function)]`.
+fn parse_validations(attrs: &[Attribute]) -> syn::Result<Vec<TokenStream2>> {
+    let mut validations = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("validate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                let range = meta.value()?.parse::<Expr>()?;
+                validations.push(quote!(range = #range));
+            } else if meta.path.is_ident("with") {
+                let function = meta.value()?.parse::<Path>()?;
+                validations.push(quote!(with = #function));
+            } else if meta.path.is_ident("non_empty") {
+                validations.push(quote!(non_empty));
+            } else {
+                return Err(meta.error("expected `range`, `non_empty` or `with` here"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(validations)
+}
+
+struct Field {
+    member: Member,
+    name: String,
+    ty: Type,
+    attributes: FieldAttributes,
+}
+
+impl Field {
+    fn all(fields: &Fields) -> syn::Result<Vec<Field>> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let (member, name) = match &field.ident {
+                    Some(ident) => (Member::from(ident.clone()), ident.unraw().to_string()),
+                    None => (Member::from(i), i.to_string()),
+                };
+                Ok(Field {
+                    member,
+                    name,
+                    ty: field.ty.clone(),
+                    attributes: parse_field_attributes(&field.attrs)?,
+                })
+            })
+            .collect()
+    }
+    // One bound per level of nesting.
+    fn bounds(&self) -> Vec<WherePredicate> {
+        let mut ty = self.ty.clone();
+        let mut bounds = Vec::new();
+        for _ in &self.attributes.separators {
+            bounds.push(parse_quote!(#ty: ::aoc_utils::SeparatorParsable<'parse_text>));
+            ty = parse_quote!(<#ty as ::aoc_utils::SeparatorParsable<'parse_text>>::Item);
+        }
+        if self.attributes.tuple_separator.is_some() {
+            bounds.push(parse_quote!(#ty: ::aoc_utils::TupleParsable<'parse_text>));
+        } else {
+            bounds.push(parse_quote!(#ty: ::aoc_utils::Parsable<'parse_text>));
+        }
+        bounds
+    }
+    // Parses `field_text`, splitting it by each separator in turn, and validates the result.
+    fn parse(&self) -> TokenStream2 {
+        let mut ty = self.ty.clone();
+        let mut collections = Vec::new();
+        for separator in &self.attributes.separators {
+            collections.push((ty.clone(), separator));
+            ty = parse_quote!(<#ty as ::aoc_utils::SeparatorParsable<'parse_text>>::Item);
+        }
+        let mut parse = match &self.attributes.tuple_separator {
+            Some(separator) => quote! {
+                <#ty as ::aoc_utils::TupleParsable<'parse_text>>::parse_tuple(
+                    field_text,
+                    ::aoc_utils::make_pattern!(#separator),
+                )
+            },
+            None => quote!(<#ty as ::aoc_utils::Parsable<'parse_text>>::parse(field_text)),
+        };
+        for (ty, separator) in collections.into_iter().rev() {
+            parse = quote! {
+                <#ty as ::aoc_utils::SeparatorParsable<'parse_text>>::parse_separated_with(
+                    field_text,
+                    ::aoc_utils::make_pattern!(#separator),
+                    |field_text| #parse,
+                )
+            };
+        }
+        let validations = &self.attributes.validations;
+        if validations.is_empty() {
+            return parse;
+        }
+        quote! {
+            #parse.and_then(|value| {
+                let field = &value;
+                ::aoc_utils::validate_field!(field, #(#validations),*)?;
+                Ok(value)
+            })
+        }
+    }
+}
+
+// Without a format, a single field takes the whole text.
+fn segments(format: Option<&LitStr>, fields: &[Field], span: Span) -> syn::Result<Vec<Segment>> {
+    let Some(format) = format else {
+        return match fields {
+            [field] => Ok(vec![Segment::Field(field.name.clone())]),
+            _ => Err(syn::Error::new(
+                span,
+                "`#[parse(format = \"...\")]` is needed unless there is exactly one field",
+            )),
+        };
+    };
+    let error = |message: String| syn::Error::new(format.span(), message);
+    let segments = parse_format(&format.value()).map_err(error)?;
+    let placeholders = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Field(name) => Some(name),
+            Segment::Literal(_) => None,
+        })
+        .collect::<Vec<_>>();
+    for (i, placeholder) in placeholders.iter().enumerate() {
+        if !fields.iter().any(|field| field.name == **placeholder) {
+            return Err(error(format!("there is no field `{}`", placeholder)));
+        }
+        if placeholders[..i].contains(placeholder) {
+            return Err(error(format!(
+                "`{{{}}}` is used more than once",
+                placeholder
+            )));
+        }
+    }
+    if let Some(field) = fields
+        .iter()
+        .find(|field| !placeholders.contains(&&field.name))
+    {
+        return Err(error(format!("the format is missing `{{{}}}`", field.name)));
+    }
+    // Without a literal, only the width tells where a field ends.
+    for pair in segments.windows(2) {
+        if let [Segment::Field(name), Segment::Field(next)] = pair {
+            let field = fields.iter().find(|field| field.name == *name).unwrap();
+            if field.attributes.width.is_none() {
+                return Err(error(format!(
+                    "`{{{}}}` and `{{{}}}` need a literal between them, or a `width` for `{}`",
+                    name, next, name
+                )));
+            }
+        }
+    }
+    Ok(segments)
+}
+
+// A body returning `Result<(Self, usize), ParseError>` that reads `text` in the order of the format,
+// along with how much of it was left unread.
+fn parse_fields(constructor: TokenStream2, fields: &[Field], segments: &[Segment]) -> TokenStream2 {
+    // Without fields the literal has to match the whole text, so `OR` doesn't parse `XOR`.
+    if let [Segment::Literal(literal)] = segments {
+        let anchored = format!("^(?:{})$", literal);
+        return quote! {
+            if ::aoc_utils::make_regex!(#anchored).is_match(text) {
+                Ok((#constructor {}, 0))
+            } else {
+                let kind = ::aoc_utils::ParseErrorKind::SkipNotFound { pattern: #literal.to_string() };
+                Err(::aoc_utils::ParseError::new(kind, text))
+            }
+        };
+    }
+    let mut statements = vec![quote! {
+        let mut buffer = ::aoc_utils::ParseBuffer::new(text);
+    }];
+    if let Some(Segment::Literal(literal)) = segments.first() {
+        statements.push(quote! {
+            buffer.skip(::aoc_utils::make_pattern!(#literal))?;
+        });
+    }
+    for (i, segment) in segments.iter().enumerate() {
+        let Segment::Field(name) = segment else {
+            continue;
+        };
+        let field = fields.iter().find(|field| field.name == *name).unwrap();
+        let read = match (&field.attributes.width, segments.get(i + 1)) {
+            (Some(width), Some(Segment::Literal(literal))) => quote! {
+                buffer.read_width(#width).and_then(|field_text| {
+                    buffer.skip(::aoc_utils::make_pattern!(#literal))?;
+                    Ok(field_text)
+                })
+            },
+            (Some(width), _) => quote!(buffer.read_width(#width)),
+            (None, Some(Segment::Literal(literal))) => {
+                quote!(buffer.read_until(::aoc_utils::make_pattern!(#literal)))
+            }
+            (None, _) => quote!(Ok::<_, ::aoc_utils::ParseError>(buffer.read_to_end())),
+        };
+        let parse = field.parse();
+        let binding = format_ident!("parsed_{}", name);
+        statements.push(quote! {
+            let #binding = #read
+                .and_then(|field_text| #parse.map_err(|e| e.at(field_text)))
+                .map_err(|e| e.with_field(#name))?;
+        });
+    }
+    let members = fields.iter().map(|field| &field.member);
+    let bindings = fields
+        .iter()
+        .map(|field| format_ident!("parsed_{}", field.name));
+    quote! {
+        #(#statements)*
+        Ok((#constructor { #(#members: #bindings),* }, buffer.remaining().len()))
+    }
+}
+
+// A body returning `Result<Self, ParseError>` that reads each field from its group of `captures`,
+// or from an empty text if the group didn't match.
+fn parse_captures(
+    constructor: TokenStream2,
+    fields: &[Field],
+    pattern: &LitStr,
+) -> syn::Result<TokenStream2> {
+    let names = fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    check_captures(&pattern.value(), &names).map_err(|e| syn::Error::new(pattern.span(), e))?;
+    if let Some(width) = fields
+        .iter()
+        .find_map(|field| field.attributes.width.as_ref())
+    {
+        return Err(syn::Error::new(
+            width.span(),
+            "`width` only applies to formats, not to `captures`",
+        ));
+    }
+    let statements = fields.iter().map(|field| {
+        let name = &field.name;
+        let group = match name.parse::<usize>() {
+            Ok(i) => {
+                let i = i + 1;
+                quote!(captures.get(#i))
+            }
+            Err(_) => quote!(captures.name(#name)),
+        };
+        let parse = field.parse();
+        let binding = format_ident!("parsed_{}", name);
+        quote! {
+            let field_text = #group.map_or_else(
+                || &captures.get(0).unwrap().as_str()[..0],
+                |group| group.as_str(),
+            );
+            let #binding = #parse
+                .map_err(|e| e.at(field_text))
+                .map_err(|e| e.with_field(#name))?;
+        }
+    });
+    let members = fields.iter().map(|field| &field.member);
+    let bindings = fields
+        .iter()
+        .map(|field| format_ident!("parsed_{}", field.name));
+    Ok(quote! {
+        #(#statements)*
+        Ok(#constructor { #(#members: #bindings),* })
+    })
+}
+
+// Finds the `captures` of the anchored regex of a variant, then reads them like
+// `CapturesParsable::from_captures` does.
+fn parse_anchored(index: usize, pattern: &LitStr) -> TokenStream2 {
+    let anchored = format!("^(?:{})$", pattern.value());
+    quote! {
+        match ::aoc_utils::make_regex!(#anchored).captures(text) {
+            Some(captures) => {
+                <Self as ::aoc_utils::CapturesParsable<'parse_text>>::from_captures(#index, &captures)
+            }
+            None => {
+                let kind = ::aoc_utils::ParseErrorKind::SkipNotFound { pattern: #pattern.to_string() };
+                Err(::aoc_utils::ParseError::new(kind, text))
+            }
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let name_text = name.to_string();
+    let mut all_fields = Vec::new();
+    // The name, the regex and the body of `from_captures` of each variant, if they have `captures`.
+    let mut captures = Vec::new();
+    let mut longest_match = false;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = Field::all(&data.fields)?;
+            let parse = match parse_pattern(&input.attrs)? {
+                Some(Pattern::Captures(pattern)) => {
+                    captures.push((
+                        name_text.clone(),
+                        pattern.clone(),
+                        parse_captures(quote!(Self), &fields, &pattern)?,
+                    ));
+                    let parse = parse_anchored(0, &pattern);
+                    quote!(#parse.map(|parsed| (parsed, 0)))
+                }
+                format => {
+                    let format = match &format {
+                        Some(Pattern::Format(format)) => Some(format),
+                        _ => None,
+                    };
+                    let segments = segments(format, &fields, name.span())?;
+                    parse_fields(quote!(Self), &fields, &segments)
+                }
+            };
+            all_fields.extend(fields);
+            quote! {
+                let parse_fields = || -> Result<(Self, usize), ::aoc_utils::ParseError> { #parse };
+                parse_fields()
+                    .map(|(parsed, _)| parsed)
+                    .map_err(|e| e.with_root(#name_text))
+            }
+        }
+        Data::Enum(data) => {
+            longest_match = parse_longest_match(&input.attrs)?;
+            if data.variants.is_empty() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "an enum without variants can't be parsed",
+                ));
+            }
+            let patterns = data
+                .variants
+                .iter()
+                .map(|variant| parse_pattern(&variant.attrs))
+                .collect::<syn::Result<Vec<_>>>()?;
+            let has_captures = patterns
+                .iter()
+                .any(|pattern| matches!(pattern, Some(Pattern::Captures(_))));
+            let mut variants = Vec::new();
+            for (i, (variant, pattern)) in data.variants.iter().zip(patterns).enumerate() {
+                let fields = Field::all(&variant.fields)?;
+                let variant_name = &variant.ident;
+                let variant_text = variant_name.to_string();
+                let parse = match pattern {
+                    Some(Pattern::Captures(pattern)) => {
+                        let parse = parse_captures(quote!(Self::#variant_name), &fields, &pattern)?;
+                        captures.push((
+                            variant_text.clone(),
+                            pattern.clone(),
+                            quote! {
+                                (|| -> Result<Self, ::aoc_utils::ParseError> { #parse })()
+                                    .map_err(|e| e.with_root(#variant_text))
+                            },
+                        ));
+                        let parse = parse_anchored(i, &pattern);
+                        quote!(#parse.map(|parsed| (parsed, 0)))
+                    }
+                    _ if has_captures => {
+                        return Err(syn::Error::new(
+                            variant_name.span(),
+                            "every variant needs `captures` when one of them has it",
+                        ))
+                    }
+                    format => {
+                        let format = match &format {
+                            Some(Pattern::Format(format)) => Some(format),
+                            _ => None,
+                        };
+                        let segments = segments(format, &fields, variant_name.span())?;
+                        parse_fields(quote!(Self::#variant_name), &fields, &segments)
+                    }
+                };
+                variants.push(quote! {
+                    (
+                        #variant_text,
+                        &|text: &'parse_text str| -> Result<(Self, usize), ::aoc_utils::ParseError> {
+                            #parse
+                        },
+                    ),
+                });
+                all_fields.extend(fields);
+            }
+            quote! {
+                ::aoc_utils::parse_variants(text, #longest_match, &[#(#variants)*])
+                    .map_err(|e| e.with_root(#name_text))
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "unions can't be parsed",
+            ))
+        }
+    };
+    // The text has to outlive the lifetimes of the type, so that its fields can borrow from it.
+    let mut impl_generics = input.generics.clone();
+    let lifetimes = input.generics.lifetimes().map(|param| &param.lifetime);
+    impl_generics
+        .params
+        .insert(0, parse_quote!('parse_text: #(#lifetimes)+*));
+    if !input.generics.params.is_empty() {
+        let where_clause = impl_generics.make_where_clause();
+        where_clause
+            .predicates
+            .extend(all_fields.iter().flat_map(Field::bounds));
+    }
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+    let captures_impl = (!captures.is_empty()).then(|| {
+        let names = captures.iter().map(|(name, _, _)| name);
+        let patterns = captures.iter().map(|(_, pattern, _)| pattern);
+        let parses = captures.iter().map(|(_, _, parse)| parse);
+        let indices = 0..captures.len();
+        let longest_match = longest_match.then(|| {
+            quote! {
+                fn longest_match() -> bool {
+                    true
+                }
+            }
+        });
+        quote! {
+            impl #impl_generics ::aoc_utils::CapturesParsable<'parse_text> for #name #type_generics #where_clause {
+                fn regexes() -> Vec<(&'static str, &'static ::aoc_utils::Regex)> {
+                    vec![#((#names, ::aoc_utils::make_regex!(#patterns))),*]
+                }
+                #longest_match
+                fn from_captures(
+                    index: usize,
+                    captures: &::aoc_utils::Captures<'parse_text>,
+                ) -> Result<Self, ::aoc_utils::ParseError> {
+                    let parse_fields = || -> Result<Self, ::aoc_utils::ParseError> {
+                        match index {
+                            #(#indices => { #parses })*
+                            _ => panic!("{} has no regex {}", #name_text, index),
+                        }
+                    };
+                    parse_fields().map_err(|e| e.with_root(#name_text))
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics ::aoc_utils::Parsable<'parse_text> for #name #type_generics #where_clause {
+            fn parse(text: &'parse_text str) -> Result<Self, ::aoc_utils::ParseError> {
+                #body
+            }
+        }
+        #captures_impl
+    })
+}
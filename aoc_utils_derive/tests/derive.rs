@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use aoc_utils::{Digits, MyResult, Parsable, ParseErrorKind};
+
+#[derive(Parsable, PartialEq, Eq, Debug)]
+#[parse(format = "p={p_x},{p_y} v={velocity}")]
+struct DerivedRobot {
+    /// Doc comments are fine.
+    pub p_x: i64,
+    p_y: i64,
+    #[parse(sep = ",")]
+    velocity: Vec<i64>,
+}
+
+#[derive(Parsable, PartialEq, Eq, Debug)]
+#[parse(format = "{1}-{0}")]
+struct DerivedPair<T>(T, String);
+
+#[derive(Parsable, PartialEq, Eq, Debug)]
+struct DerivedNested<T> {
+    #[parse(sep("\n", ","))]
+    rows: Vec<Vec<T>>,
+}
+
+#[derive(Parsable, Debug)]
+struct DerivedPoints<T> {
+    #[parse(sep = "\n", tuple_sep = ",")]
+    points: Vec<(T, T)>,
+}
+
+#[derive(Parsable, PartialEq, Eq, Debug)]
+#[parse(format = "{name}: {value}")]
+struct DerivedBorrowed<'a> {
+    name: &'a str,
+    value: i32,
+}
+
+#[derive(Parsable, Debug)]
+#[parse(format = "{code}{digits}")]
+struct DerivedWidths {
+    #[parse(width = 2)]
+    code: String,
+    digits: Digits,
+}
+
+#[derive(Parsable, Debug)]
+struct DerivedMap {
+    #[parse(sep = ", *", key_value_sep = "=")]
+    values: BTreeMap<String, i32>,
+}
+
+#[derive(Parsable, PartialEq, Eq, Debug)]
+enum DerivedInstruction {
+    #[parse(format = "{name}={value}")]
+    Set { name: String, value: i32 },
+    #[parse(format = "{0}-")]
+    Dash(String),
+    #[parse(format = "clear")]
+    Clear,
+}
+
+#[test]
+fn derive_struct() -> MyResult<()> {
+    assert_eq!(
+        DerivedRobot::parse("p=0,4 v=3,-3")?,
+        DerivedRobot {
+            p_x: 0,
+            p_y: 4,
+            velocity: vec![3, -3]
+        }
+    );
+    assert_eq!(
+        DerivedPair::<u8>::parse("ab-12")?,
+        DerivedPair(12, "ab".to_string())
+    );
+    assert_eq!(
+        DerivedNested::<u8>::parse("1,2\n3")?.rows,
+        vec![vec![1, 2], vec![3]]
+    );
+    assert_eq!(
+        DerivedBorrowed::parse("x: 3")?,
+        DerivedBorrowed {
+            name: "x",
+            value: 3
+        }
+    );
+    let parsed = DerivedWidths::parse("ab012")?;
+    assert_eq!(
+        (parsed.code.as_str(), parsed.digits.0),
+        ("ab", vec![0, 1, 2])
+    );
+    assert_eq!(
+        DerivedPoints::<u8>::parse("1,2\n3,4")?.points,
+        vec![(1, 2), (3, 4)]
+    );
+    assert_eq!(
+        DerivedMap::parse("a=1, b=2")?.values,
+        BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+    );
+    let error = DerivedRobot::parse("p=0,x v=3").unwrap_err();
+    assert_eq!(error.path(), "DerivedRobot.p_y");
+    assert!(matches!(error.kind(), ParseErrorKind::FromStr { .. }));
+    Ok(())
+}
+
+#[test]
+fn derive_enum() -> MyResult<()> {
+    assert_eq!(
+        DerivedInstruction::parse("ab=3")?,
+        DerivedInstruction::Set {
+            name: "ab".to_string(),
+            value: 3
+        }
+    );
+    assert_eq!(
+        DerivedInstruction::parse("ab-")?,
+        DerivedInstruction::Dash("ab".to_string())
+    );
+    assert_eq!(
+        DerivedInstruction::parse("clear")?,
+        DerivedInstruction::Clear
+    );
+    assert!(DerivedInstruction::parse("clears").is_err());
+    let error = DerivedInstruction::parse("ab").unwrap_err();
+    assert_eq!(error.path(), "DerivedInstruction");
+    assert!(matches!(
+        error.kind(),
+        ParseErrorKind::NoVariantMatched { errors } if errors.len() == 3
+    ));
+    Ok(())
+}